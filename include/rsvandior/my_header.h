@@ -38,6 +38,11 @@ enum class TokenType {
     EqualEqual,
     LessEqual,
     GreaterEqual,
+    OrEqual,
+    AndEqual,
+    ShiftLeft,
+    ShiftRight,
+    Arrow,
     Dot,
     Identifier,
     Char,
@@ -52,6 +57,9 @@ enum class TokenType {
     KFun,
     KReturn,
     KNullptr,
+    KConst,
+    KContinue,
+    KReserved,
     OpenParenthesis,
     OpenSqParenthesis,
     OpenCurParenthesis,
@@ -76,47 +84,126 @@ enum class TokenType {
     TypeString,
     TypeBool,
     Comment,
+    DocComment,
     Unknown,
     Eoft,
 };
 
+struct Span {
+    size_t start;
+    size_t end;
+
+    Span(size_t const& start,
+         size_t const& end)
+      : start(start),
+        end(end)
+    {}
+
+    bool operator==(const Span& other) const {
+        return start == other.start &&
+               end == other.end;
+    }
+};
+
 struct CodeSourceLocation {
     const char *file_name;
     size_t line;
     size_t column;
+    size_t end_line;
+    size_t end_column;
 
     CodeSourceLocation(const char *const& file_name,
                        size_t const& line,
-                       size_t const& column)
+                       size_t const& column,
+                       size_t const& end_line,
+                       size_t const& end_column)
       : file_name(file_name),
         line(line),
-        column(column)
+        column(column),
+        end_line(end_line),
+        end_column(end_column)
     {}
 
     bool operator==(const CodeSourceLocation& other) const {
         return file_name == other.file_name &&
                line == other.line &&
-               column == other.column;
+               column == other.column &&
+               end_line == other.end_line &&
+               end_column == other.end_column;
+    }
+};
+
+enum class NumericKind {
+    None,
+    Integer,
+    Float,
+};
+
+struct NumericLiteral {
+    NumericKind kind;
+    uint32_t radix;
+    bool imaginary;
+    bool is_f32;
+    uint64_t integer_value;
+    double float_value;
+
+    NumericLiteral(NumericKind const& kind,
+                   uint32_t const& radix,
+                   bool const& imaginary,
+                   bool const& is_f32,
+                   uint64_t const& integer_value,
+                   double const& float_value)
+      : kind(kind),
+        radix(radix),
+        imaginary(imaginary),
+        is_f32(is_f32),
+        integer_value(integer_value),
+        float_value(float_value)
+    {}
+
+    bool operator==(const NumericLiteral& other) const {
+        return kind == other.kind &&
+               radix == other.radix &&
+               imaginary == other.imaginary &&
+               is_f32 == other.is_f32 &&
+               integer_value == other.integer_value &&
+               float_value == other.float_value;
     }
 };
 
 struct Token {
     TokenType token_type;
     const char *value;
+    Span span;
     CodeSourceLocation source_location;
+    NumericLiteral numeric;
+    const char *cooked;
+    size_t cooked_len;
 
     Token(TokenType const& token_type,
           const char *const& value,
-          CodeSourceLocation const& source_location)
+          Span const& span,
+          CodeSourceLocation const& source_location,
+          NumericLiteral const& numeric,
+          const char *const& cooked,
+          size_t const& cooked_len)
       : token_type(token_type),
         value(value),
-        source_location(source_location)
+        span(span),
+        source_location(source_location),
+        numeric(numeric),
+        cooked(cooked),
+        cooked_len(cooked_len)
     {}
 
     bool operator==(const Token& other) const {
         return token_type == other.token_type &&
                value == other.value &&
-               source_location == other.source_location;
+               span == other.span &&
+               source_location == other.source_location &&
+               numeric == other.numeric &&
+               cooked == other.cooked &&
+               cooked_len == other.cooked_len;
     }
 };
 
@@ -125,9 +212,9 @@ extern "C" {
 #endif
 
     typedef struct {
-        Token* tokens;
-        size_t size;
+        Token* ptr;
         size_t capacity;
+        size_t size;
     } TokenDynamicArray;
 
 
@@ -136,6 +223,10 @@ int32_t add_numbers(int32_t a, int32_t b);
 int32_t subtract_numbers(int32_t a, int32_t b);
 
 TokenDynamicArray tokenize_w(const char *file_name, const char *input);
+
+void vndr_token_free(Token *token);
+
+void vndr_token_array_free(TokenDynamicArray array);
 #ifdef __cplusplus
 }
 #endif
\ No newline at end of file
@@ -6,18 +6,50 @@
 #pragma once
 
 #include "my_header.h"
+#include <cstddef>
+#include <stdexcept>
 #include <string>
 #include <string_view>
-#include <vector>
+#include <utility>
 
 namespace vnd {
-       inline std::vector<::Token> wrap_tokenize(const std::string_view &input, std::string_view fileName) {
-  const auto array = tokenize_w(input.data(), fileName.data());
-         if (!array.tokens || array.size >array.capacity) {
-           throw std::runtime_error("Invalid DynamicArray state");
-         }
+    /**
+     * @brief Owns the tokens returned by tokenize_w and releases them with vndr_token_array_free.
+     *
+     * The token values and file names point into memory owned by this object, so views built on
+     * them (e.g. vnd::Token) must not outlive it.
+     */
+    class TokenArray {
+    public:
+        explicit TokenArray(TokenDynamicArray array) noexcept : _array(array) {}
+        TokenArray(const TokenArray &) = delete;
+        TokenArray &operator=(const TokenArray &) = delete;
+        TokenArray(TokenArray &&other) noexcept : _array(std::exchange(other._array, TokenDynamicArray{nullptr, 0, 0})) {}
+        TokenArray &operator=(TokenArray &&other) noexcept {
+            if(this != &other) {
+                vndr_token_array_free(_array);
+                _array = std::exchange(other._array, TokenDynamicArray{nullptr, 0, 0});
+            }
+            return *this;
+        }
+        ~TokenArray() { vndr_token_array_free(_array); }
 
-         std::vector<::Token> vec(array.tokens, array.tokens + array.size); // Transfer elements
-         return vec;
-       }
+        [[nodiscard]] const ::Token *begin() const noexcept { return _array.ptr; }
+        [[nodiscard]] const ::Token *end() const noexcept { return _array.ptr + _array.size; }
+        [[nodiscard]] std::size_t size() const noexcept { return _array.size; }
+        [[nodiscard]] bool empty() const noexcept { return _array.size == 0; }
+        [[nodiscard]] const ::Token &operator[](std::size_t index) const noexcept { return _array.ptr[index]; }
+
+    private:
+        TokenDynamicArray _array;
+    };
+
+    inline TokenArray wrap_tokenize(std::string_view fileName, std::string_view input) {
+        // tokenize_w expects NUL-terminated strings, which a string_view does not guarantee.
+        const std::string fileNameStr{fileName};
+        const std::string inputStr{input};
+        TokenArray array{tokenize_w(fileNameStr.c_str(), inputStr.c_str())};
+        if(array.begin() == nullptr) { throw std::runtime_error("Invalid DynamicArray state"); }
+        return array;
+    }
 }
\ No newline at end of file
@@ -7,6 +7,7 @@
 
 #include "lexer/Token.hpp"
 #include "rsvandior/my_header_wrapper.hpp"
+#include <vector>
 
 inline vnd::TokenType tokenType_to_vnd_tokenType(::TokenType tokentype) {
   switch (tokentype) {
@@ -79,6 +80,7 @@ inline vnd::TokenType tokenType_to_vnd_tokenType(::TokenType tokentype) {
   case ::TokenType::KMain:
     return vnd::TokenType::K_MAIN;
   case ::TokenType::KVar:
+  case ::TokenType::KConst:
     return vnd::TokenType::K_VAR;
   case ::TokenType::KIf:
     return vnd::TokenType::K_IF;
@@ -89,6 +91,7 @@ inline vnd::TokenType tokenType_to_vnd_tokenType(::TokenType tokentype) {
   case ::TokenType::KFor:
     return vnd::TokenType::K_FOR;
   case ::TokenType::KBreak:
+  case ::TokenType::KContinue:
     return vnd::TokenType::K_BREAK;
   case ::TokenType::KFun:
     return vnd::TokenType::K_FUN;
@@ -96,6 +99,9 @@ inline vnd::TokenType tokenType_to_vnd_tokenType(::TokenType tokentype) {
     return vnd::TokenType::K_RETURN;
   case ::TokenType::KNullptr:
     return vnd::TokenType::K_NULLPTR;
+  // The C++ keyword table has no reserved words, so they lex as identifiers there.
+  case ::TokenType::KReserved:
+    return vnd::TokenType::IDENTIFIER;
   case ::TokenType::OpenParenthesis:
     return vnd::TokenType::OPEN_PARENTESIS;
   case ::TokenType::OpenSqParenthesis:
@@ -143,6 +149,7 @@ inline vnd::TokenType tokenType_to_vnd_tokenType(::TokenType tokentype) {
   case ::TokenType::TypeBool:
     return vnd::TokenType::TYPE_BOOL;
   case ::TokenType::Comment:
+  case ::TokenType::DocComment:
     return vnd::TokenType::COMMENT;
   case ::TokenType::Unknown:
     return vnd::TokenType::UNKNOWN;
@@ -163,7 +170,7 @@ inline vnd::Token to_vnd_token(const ::Token &token)
   return vnd::Token(tokenType_to_vnd_tokenType(token.token_type),std::string_view{token.value}, to_vnd_codeSourceLocation(token.source_location));
 }
 
-inline std::vector<vnd::Token> to_vector_vnd_tokens(const std::vector<::Token> &tokens)
+inline std::vector<vnd::Token> to_vector_vnd_tokens(const vnd::TokenArray &tokens)
 {
   std::vector<vnd::Token> vec;
   for (const auto &token : tokens) {
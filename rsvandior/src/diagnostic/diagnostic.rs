@@ -0,0 +1,89 @@
+use std::fmt;
+
+use super::label::Label;
+use super::severity::Severity;
+use crate::token::{FileId, Span};
+
+/// A problem found in a source file by any phase of the compiler, ready to be rendered with
+/// `Renderer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<String>,
+    pub message: String,
+    pub file_id: FileId,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, file_id: FileId, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: None,
+            message: message.into(),
+            file_id,
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+        }
+    }
+
+    pub fn error(file_id: FileId, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, file_id, message)
+    }
+
+    pub fn warning(file_id: FileId, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, file_id, message)
+    }
+
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_primary_label(self, span: Span, message: impl Into<String>) -> Self {
+        self.with_label(Label::primary(span, message))
+    }
+
+    pub fn with_secondary_label(self, span: Span, message: impl Into<String>) -> Self {
+        self.with_label(Label::secondary(span, message))
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    /// Span of the first primary label, where the diagnostic is reported.
+    pub fn primary_span(&self) -> Option<Span> {
+        self.labels
+            .iter()
+            .find(|label| label.is_primary())
+            .map(|label| label.span)
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.code {
+            Some(code) => write!(f, "{}[{}]: {}", self.severity, code, self.message),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
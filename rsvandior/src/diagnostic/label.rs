@@ -0,0 +1,39 @@
+use crate::token::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LabelStyle {
+    /// Where the problem is, underlined with `^`.
+    Primary,
+    /// Related code that explains it, underlined with `-`.
+    Secondary,
+}
+
+/// A message attached to a span of the source a diagnostic refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub style: LabelStyle,
+    pub span: Span,
+    pub message: String,
+}
+
+impl Label {
+    pub fn new(style: LabelStyle, span: Span, message: impl Into<String>) -> Self {
+        Self {
+            style,
+            span,
+            message: message.into(),
+        }
+    }
+
+    pub fn primary(span: Span, message: impl Into<String>) -> Self {
+        Self::new(LabelStyle::Primary, span, message)
+    }
+
+    pub fn secondary(span: Span, message: impl Into<String>) -> Self {
+        Self::new(LabelStyle::Secondary, span, message)
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.style == LabelStyle::Primary
+    }
+}
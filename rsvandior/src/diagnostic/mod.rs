@@ -0,0 +1,10 @@
+#[allow(clippy::module_inception)]
+pub mod diagnostic;
+pub mod label;
+pub mod renderer;
+pub mod severity;
+
+pub use diagnostic::*;
+pub use label::*;
+pub use renderer::*;
+pub use severity::*;
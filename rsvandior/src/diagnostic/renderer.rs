@@ -0,0 +1,221 @@
+use std::fmt::Write;
+
+use super::diagnostic::Diagnostic;
+use super::label::Label;
+use crate::lexer::LineIndex;
+
+const BOLD: &str = "\x1b[1m";
+const BLUE: &str = "\x1b[1;34m";
+const RESET: &str = "\x1b[0m";
+
+/// Prints diagnostics with the source lines they refer to, underlining every label:
+///
+/// ```text
+/// error[L0001]: unexpected character
+///  --> main.vn:1:9
+///   |
+/// 1 | var a = @;
+///   |         ^ '@'
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Renderer {
+    pub colors: bool,
+}
+
+impl Renderer {
+    pub fn new(colors: bool) -> Self {
+        Self { colors }
+    }
+
+    /// `source` must be the text of the file the diagnostic refers to.
+    pub fn render(&self, diagnostic: &Diagnostic, source: &str) -> String {
+        let line_index = LineIndex::new(source);
+        let mut labels: Vec<(usize, &Label)> = diagnostic
+            .labels
+            .iter()
+            .map(|label| (line_index.line_col(label.span.start).0, label))
+            .collect();
+        labels.sort_by_key(|(line, label)| (*line, label.span.start));
+        let gutter_width = labels.last().map_or(0, |(line, _)| line.to_string().len());
+        let blank = " ".repeat(gutter_width);
+
+        let mut output = String::new();
+        let severity = self.paint(diagnostic.severity.color(), diagnostic.severity.as_str());
+        match &diagnostic.code {
+            Some(code) => {
+                let code = self.paint(diagnostic.severity.color(), &format!("[{}]", code));
+                write!(output, "{}{}", severity, code).unwrap();
+            }
+            None => output.push_str(&severity),
+        }
+        writeln!(
+            output,
+            "{}",
+            self.paint(BOLD, &format!(": {}", diagnostic.message))
+        )
+        .unwrap();
+
+        if let Some(span) = diagnostic
+            .primary_span()
+            .or(labels.first().map(|(_, label)| label.span))
+        {
+            let (line, column) = line_index.line_col(span.start);
+            writeln!(
+                output,
+                "{}{} {}:{}:{}",
+                blank,
+                self.paint(BLUE, "-->"),
+                diagnostic.file_id.name(),
+                line,
+                column + 1
+            )
+            .unwrap();
+            writeln!(output, "{} {}", blank, self.paint(BLUE, "|")).unwrap();
+        }
+
+        let mut previous_line: Option<usize> = None;
+        for (line, label) in &labels {
+            if previous_line != Some(*line) {
+                if previous_line.is_some_and(|previous| previous + 1 < *line) {
+                    writeln!(output, "{}", self.paint(BLUE, "...")).unwrap();
+                }
+                let text = line_index
+                    .line_span(*line)
+                    .map_or("", |range| &source[range])
+                    .trim_end_matches('\r')
+                    .replace('\t', " ");
+                writeln!(
+                    output,
+                    "{} {} {}",
+                    self.paint(BLUE, &format!("{:>width$}", line, width = gutter_width)),
+                    self.paint(BLUE, "|"),
+                    text
+                )
+                .unwrap();
+                previous_line = Some(*line);
+            }
+            writeln!(
+                output,
+                "{} {} {}",
+                blank,
+                self.paint(BLUE, "|"),
+                self.underline(diagnostic, label, *line, &line_index, source)
+            )
+            .unwrap();
+        }
+
+        for note in &diagnostic.notes {
+            writeln!(output, "{} {} {}", blank, self.paint(BOLD, "= note:"), note).unwrap();
+        }
+        for help in &diagnostic.help {
+            writeln!(output, "{} {} {}", blank, self.paint(BOLD, "= help:"), help).unwrap();
+        }
+        output
+    }
+
+    /// Marker line of a label; spans reaching past their first line are underlined up to its end.
+    fn underline(
+        &self,
+        diagnostic: &Diagnostic,
+        label: &Label,
+        line: usize,
+        line_index: &LineIndex,
+        source: &str,
+    ) -> String {
+        let (_, start_column) = line_index.line_col(label.span.start);
+        let (end_line, end_column) = line_index.line_col(label.span.end);
+        let end_column = if end_line == line {
+            end_column
+        } else {
+            line_index
+                .line_span(line)
+                .map_or(start_column, |range| source[range].chars().count())
+        };
+        let (marker, color) = if label.is_primary() {
+            ('^', diagnostic.severity.color())
+        } else {
+            ('-', BLUE)
+        };
+        let markers = marker
+            .to_string()
+            .repeat(end_column.saturating_sub(start_column).max(1));
+        let underline = if label.message.is_empty() {
+            markers
+        } else {
+            format!("{} {}", markers, label.message)
+        };
+        format!(
+            "{}{}",
+            " ".repeat(start_column),
+            self.paint(color, &underline)
+        )
+    }
+
+    fn paint(&self, color: &str, text: &str) -> String {
+        if self.colors {
+            format!("{}{}{}", color, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::{FileId, Span};
+
+    #[test]
+    fn test_render_primary_and_secondary_labels() {
+        let source = "var a: i8 = 3\nvar b: string = a\n";
+        let diagnostic = Diagnostic::error(FileId::intern("main.vn"), "mismatched types")
+            .with_code("E0100")
+            .with_primary_label(Span::new(30, 31), "expected `string`, found `i8`")
+            .with_secondary_label(Span::new(7, 9), "declared here")
+            .with_note("`a` is an integer")
+            .with_help("convert it with a format string");
+        assert_eq!(
+            Renderer::new(false).render(&diagnostic, source),
+            "error[E0100]: mismatched types\n \
+             --> main.vn:2:17\n  \
+             |\n\
+             1 | var a: i8 = 3\n  \
+             |        -- declared here\n\
+             2 | var b: string = a\n  \
+             |                 ^ expected `string`, found `i8`\n  \
+             = note: `a` is an integer\n  \
+             = help: convert it with a format string\n"
+        );
+    }
+
+    #[test]
+    fn test_render_gap_and_multiline_span() {
+        let source = "a\nb\nc\nlong line\nnext";
+        let diagnostic = Diagnostic::warning(FileId::intern("main.vn"), "spans")
+            .with_primary_label(Span::new(0, 1), "")
+            .with_primary_label(Span::new(11, 20), "to the end");
+        assert_eq!(
+            Renderer::new(false).render(&diagnostic, source),
+            "warning: spans\n \
+             --> main.vn:1:1\n  \
+             |\n\
+             1 | a\n  \
+             | ^\n\
+             ...\n\
+             4 | long line\n  \
+             |      ^^^^ to the end\n"
+        );
+    }
+
+    #[test]
+    fn test_render_with_colors() {
+        let diagnostic = Diagnostic::error(FileId::intern("main.vn"), "boom")
+            .with_primary_label(Span::new(0, 1), "here");
+        let rendered = Renderer::new(true).render(&diagnostic, "@");
+        assert!(rendered.starts_with("\x1b[1;31merror\x1b[0m"));
+        assert!(rendered.contains("\x1b[1;31m^ here\x1b[0m"));
+        assert!(!Renderer::new(false)
+            .render(&diagnostic, "@")
+            .contains('\x1b'));
+    }
+}
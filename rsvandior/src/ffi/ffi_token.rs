@@ -0,0 +1,252 @@
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use crate::token::{CodeSourceLocation, NumericLiteral, Span, Token, TokenType};
+
+/// C layout of `CodeSourceLocation`, exported to C++ as `CodeSourceLocation`.
+///
+/// Owns its `file_name`, which is released on drop.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiCodeSourceLocation {
+    pub file_name: *const c_char,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FfiNumericKind {
+    None,
+    Integer,
+    Float,
+}
+
+/// C layout of `Option<NumericLiteral>`, exported to C++ as `NumericLiteral`.
+///
+/// `float_value` is filled for integers too, so that C++ can read either field.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FfiNumericLiteral {
+    pub kind: FfiNumericKind,
+    pub radix: u32,
+    pub imaginary: bool,
+    pub is_f32: bool,
+    pub integer_value: u64,
+    pub float_value: f64,
+}
+
+/// C layout of `Token`, exported to C++ as `Token`.
+///
+/// Owns its `value`, `cooked` and location strings, which are released on drop. `cooked` is
+/// null unless the token is a doc comment or a string or char literal with valid escapes; it is
+/// NUL-terminated but may also contain `\0`, so `cooked_len` gives its length in bytes.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiToken {
+    pub token_type: TokenType,
+    pub value: *const c_char,
+    pub span: Span,
+    pub source_location: FfiCodeSourceLocation,
+    pub numeric: FfiNumericLiteral,
+    pub cooked: *const c_char,
+    pub cooked_len: usize,
+}
+
+fn to_c_string(value: &str) -> *const c_char {
+    // The input of `tokenize_w` comes from a C string, so it cannot contain a NUL byte.
+    CString::new(value)
+        .unwrap_or_else(|err| panic!("Tokenization failed: {}", err))
+        .into_raw()
+}
+
+/// Reclaims a string made by `to_c_string` and leaves a null pointer behind, so that releasing it
+/// twice is harmless.
+fn free_c_string(value: &mut *const c_char) {
+    if !value.is_null() {
+        drop(unsafe { CString::from_raw(*value as *mut c_char) });
+        *value = std::ptr::null();
+    }
+}
+
+fn to_c_bytes(value: &str) -> *const c_char {
+    let mut bytes = Vec::with_capacity(value.len() + 1);
+    bytes.extend_from_slice(value.as_bytes());
+    bytes.push(0);
+    Box::into_raw(bytes.into_boxed_slice()) as *mut u8 as *const c_char
+}
+
+/// Reclaims a buffer made by `to_c_bytes` from `len + 1` bytes, like `free_c_string`.
+fn free_c_bytes(value: &mut *const c_char, len: usize) {
+    if !value.is_null() {
+        let bytes = std::ptr::slice_from_raw_parts_mut(*value as *mut u8, len + 1);
+        drop(unsafe { Box::from_raw(bytes) });
+        *value = std::ptr::null();
+    }
+}
+
+impl FfiToken {
+    pub fn release_strings(&mut self) {
+        free_c_string(&mut self.value);
+        free_c_bytes(&mut self.cooked, self.cooked_len);
+        free_c_string(&mut self.source_location.file_name);
+    }
+}
+
+impl Drop for FfiToken {
+    fn drop(&mut self) {
+        free_c_string(&mut self.value);
+        free_c_bytes(&mut self.cooked, self.cooked_len);
+    }
+}
+
+impl Drop for FfiCodeSourceLocation {
+    fn drop(&mut self) {
+        free_c_string(&mut self.file_name);
+    }
+}
+
+/// Releases the strings owned by `token`, leaving null pointers in their place.
+///
+/// # Safety
+///
+/// `token` must be null or point to a token returned by `tokenize_w` that is still owned by its
+/// array; the array can still be freed afterwards with `vndr_token_array_free`.
+#[no_mangle]
+pub unsafe extern "C" fn vndr_token_free(token: *mut FfiToken) {
+    if let Some(token) = unsafe { token.as_mut() } {
+        token.release_strings();
+    }
+}
+
+impl From<&CodeSourceLocation> for FfiCodeSourceLocation {
+    fn from(location: &CodeSourceLocation) -> Self {
+        Self {
+            file_name: to_c_string(&location.file_name()),
+            line: location.line,
+            column: location.column,
+            end_line: location.end_line,
+            end_column: location.end_column,
+        }
+    }
+}
+
+impl From<Option<NumericLiteral>> for FfiNumericLiteral {
+    fn from(numeric: Option<NumericLiteral>) -> Self {
+        match numeric {
+            Some(numeric) => Self {
+                kind: if numeric.is_float() {
+                    FfiNumericKind::Float
+                } else {
+                    FfiNumericKind::Integer
+                },
+                radix: numeric.radix as u32,
+                imaginary: numeric.imaginary,
+                is_f32: numeric.is_f32,
+                integer_value: numeric.as_u64().unwrap_or_default(),
+                float_value: numeric.as_f64(),
+            },
+            None => Self {
+                kind: FfiNumericKind::None,
+                radix: 0,
+                imaginary: false,
+                is_f32: false,
+                integer_value: 0,
+                float_value: 0.0,
+            },
+        }
+    }
+}
+
+impl From<&Token<'_>> for FfiToken {
+    fn from(token: &Token<'_>) -> Self {
+        Self {
+            token_type: token.token_type.clone(),
+            value: to_c_string(token.value),
+            span: token.span,
+            source_location: FfiCodeSourceLocation::from(&token.source_location),
+            numeric: FfiNumericLiteral::from(token.numeric),
+            cooked: token.cooked.as_deref().map_or(std::ptr::null(), to_c_bytes),
+            cooked_len: token.cooked.as_deref().map_or(0, str::len),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::FileId;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_ffi_token_from_token() {
+        let token = Token::new_with_span(
+            TokenType::Identifier,
+            "test",
+            Span::new(4, 8),
+            CodeSourceLocation::new_with_end(FileId::intern("test.vn"), 1, 4, 1, 8),
+        );
+        let ffi_token = FfiToken::from(&token);
+        assert_eq!(ffi_token.token_type, TokenType::Identifier);
+        assert_eq!(
+            unsafe { CStr::from_ptr(ffi_token.value) }.to_str(),
+            Ok("test")
+        );
+        assert_eq!(ffi_token.span, Span::new(4, 8));
+        assert_eq!(
+            unsafe { CStr::from_ptr(ffi_token.source_location.file_name) }.to_str(),
+            Ok("test.vn")
+        );
+        assert_eq!(ffi_token.source_location.end_column, 8);
+        assert_eq!(ffi_token.numeric.kind, FfiNumericKind::None);
+        assert!(ffi_token.cooked.is_null());
+    }
+
+    #[test]
+    fn test_ffi_numeric_literal() {
+        let numeric = FfiNumericLiteral::from(NumericLiteral::parse("#o17").ok());
+        assert_eq!(numeric.kind, FfiNumericKind::Integer);
+        assert_eq!(numeric.radix, 8);
+        assert_eq!(numeric.integer_value, 15);
+        assert_eq!(numeric.float_value, 15.0);
+        let numeric = FfiNumericLiteral::from(NumericLiteral::parse("2.5if").ok());
+        assert_eq!(numeric.kind, FfiNumericKind::Float);
+        assert!(numeric.imaginary && numeric.is_f32);
+        assert_eq!(numeric.float_value, 2.5);
+    }
+
+    #[test]
+    fn test_ffi_token_cooked() {
+        let token = Token::new_with_cooked(
+            TokenType::String,
+            r"a\0b",
+            Span::new(0, 6),
+            CodeSourceLocation::new(FileId::intern("test.vn"), 1, 0),
+            "a\0b".into(),
+        );
+        let mut ffi_token = FfiToken::from(&token);
+        assert_eq!(ffi_token.cooked_len, 3);
+        let cooked = unsafe { std::slice::from_raw_parts(ffi_token.cooked as *const u8, 4) };
+        assert_eq!(cooked, b"a\0b\0");
+        unsafe { vndr_token_free(&mut ffi_token) };
+        assert!(ffi_token.cooked.is_null());
+    }
+
+    #[test]
+    fn test_vndr_token_free() {
+        let token = Token::new(
+            TokenType::Identifier,
+            "test",
+            CodeSourceLocation::new(FileId::intern("test.vn"), 1, 1),
+        );
+        let mut ffi_token = FfiToken::from(&token);
+        unsafe { vndr_token_free(&mut ffi_token) };
+        assert!(ffi_token.value.is_null());
+        assert!(ffi_token.cooked.is_null());
+        assert!(ffi_token.source_location.file_name.is_null());
+        unsafe { vndr_token_free(&mut ffi_token) };
+        unsafe { vndr_token_free(std::ptr::null_mut()) };
+    }
+}
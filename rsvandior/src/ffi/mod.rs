@@ -0,0 +1,7 @@
+pub mod ffi_token;
+pub mod token_dynamic_array;
+pub mod tokenize;
+
+pub use ffi_token::*;
+pub use token_dynamic_array::*;
+pub use tokenize::*;
@@ -0,0 +1,144 @@
+use super::FfiToken;
+use std::alloc::{alloc, dealloc, realloc, Layout};
+use std::ptr;
+
+/// Token buffer handed to C++ by `tokenize_w`; it owns every token and must be released with
+/// `vndr_token_array_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct TokenDynamicArray {
+    ptr: *mut FfiToken, // Puntatore all'array allocato
+    capacity: usize,    // Capacità totale dell'array
+    size: usize,        // Numero di elementi nell'array
+}
+
+impl Default for TokenDynamicArray {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenDynamicArray {
+    pub fn new() -> Self {
+        TokenDynamicArray {
+            ptr: ptr::null_mut(),
+            capacity: 0,
+            size: 0,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity == 0 {
+            return Self::new();
+        }
+        let layout = Layout::array::<FfiToken>(capacity).expect("Layout creation failed");
+        let ptr = unsafe { alloc(layout) as *mut FfiToken };
+        TokenDynamicArray {
+            ptr,
+            capacity,
+            size: 0,
+        }
+    }
+
+    pub fn push(&mut self, item: FfiToken) {
+        if self.size == self.capacity {
+            self.resize();
+        }
+        unsafe {
+            ptr::write(self.ptr.add(self.size), item);
+        }
+        self.size += 1;
+    }
+
+    fn resize(&mut self) {
+        let new_capacity = if self.capacity == 0 {
+            1
+        } else {
+            self.capacity * 2
+        };
+        let new_layout = Layout::array::<FfiToken>(new_capacity).expect("Layout creation failed");
+        let new_ptr = if self.capacity == 0 {
+            unsafe { alloc(new_layout) as *mut FfiToken }
+        } else {
+            let old_layout =
+                Layout::array::<FfiToken>(self.capacity).expect("Layout creation failed");
+            unsafe { realloc(self.ptr as *mut u8, old_layout, new_layout.size()) as *mut FfiToken }
+        };
+        self.ptr = new_ptr;
+        self.capacity = new_capacity;
+    }
+
+    pub fn pop(&mut self) -> Option<FfiToken> {
+        if self.size == 0 {
+            None
+        } else {
+            self.size -= 1;
+            unsafe { Some(ptr::read(self.ptr.add(self.size))) }
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&FfiToken> {
+        if index < self.size {
+            unsafe { Some(&*self.ptr.add(index)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut FfiToken> {
+        if index < self.size {
+            unsafe { Some(&mut *self.ptr.add(index)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, index: usize, item: FfiToken) {
+        if index < self.size {
+            unsafe {
+                *self.ptr.add(index) = item;
+            }
+        } else {
+            panic!("Index out of bounds");
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+impl Drop for TokenDynamicArray {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            self.clear();
+            let layout = Layout::array::<FfiToken>(self.capacity).expect("Layout creation failed");
+            unsafe {
+                dealloc(self.ptr as *mut u8, layout);
+            }
+        }
+    }
+}
+
+/// Releases `array`, its buffer and every string owned by its tokens.
+///
+/// # Safety
+///
+/// `array` must have been returned by `tokenize_w` and not freed yet. Pointers into its tokens
+/// must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn vndr_token_array_free(array: TokenDynamicArray) {
+    drop(array);
+}
@@ -0,0 +1,180 @@
+use std::ffi::CStr;
+
+use super::{FfiToken, TokenDynamicArray};
+use crate::lexer::Tokenizer;
+
+/// Tokenizes `input` for C++; the result must be released with `vndr_token_array_free`.
+///
+/// # Safety
+///
+/// `file_name` and `input` must be valid, NUL-terminated C strings.
+#[no_mangle]
+pub unsafe extern "C" fn tokenize_w(
+    file_name: *const std::os::raw::c_char,
+    input: *const std::os::raw::c_char,
+) -> TokenDynamicArray {
+    let c_str1 = unsafe { CStr::from_ptr(file_name) };
+    let c_str2 = unsafe { CStr::from_ptr(input) };
+    let file_name_str = c_str1.to_str().unwrap_or("Unknown");
+    let input_str = c_str2.to_str().unwrap_or("Unknown");
+    let tokenizer = Tokenizer::new(file_name_str, input_str);
+
+    // Lexical errors reach C++ as `Unknown` tokens so that they can be reported there.
+    let mut tokens = TokenDynamicArray::new();
+    let mut last_end = None;
+    for item in tokenizer {
+        let token = match item {
+            Ok(token) => token,
+            // Errors inside a token that is already in the stream need no stand-in.
+            Err(error) if last_end.is_some_and(|end| end > error.span.start) => continue,
+            Err(error) => error.to_token(),
+        };
+        last_end = Some(token.span.end);
+        tokens.push(FfiToken::from(&token));
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{vndr_token_array_free, vndr_token_free};
+    use crate::token::TokenType;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::ffi::CString;
+
+    /// Counts the bytes each thread has allocated and not yet freed, so that a test can check
+    /// that the FFI frees everything it hands out while other tests run in parallel.
+    struct CountingAllocator;
+
+    thread_local! {
+        static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+    }
+
+    fn add_live_bytes(bytes: isize) {
+        // `try_with` because the thread-local may already be gone while a thread shuts down.
+        let _ = LIVE_BYTES.try_with(|live| live.set(live.get() + bytes));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            add_live_bytes(layout.size() as isize);
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            add_live_bytes(-(layout.size() as isize));
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            add_live_bytes(new_size as isize - layout.size() as isize);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn live_bytes() -> isize {
+        LIVE_BYTES.with(Cell::get)
+    }
+
+    fn tokenize(input: &str) -> TokenDynamicArray {
+        let file_name = CString::new("test.vn").unwrap();
+        let input = CString::new(input).unwrap();
+        unsafe { tokenize_w(file_name.as_ptr(), input.as_ptr()) }
+    }
+
+    #[test]
+    fn test_tokenize_w_and_free() {
+        let array = tokenize("var a: i8 = 3 @");
+        assert_eq!(array.len(), 8);
+        let token = array.get(1).unwrap();
+        assert_eq!(token.token_type, TokenType::Identifier);
+        assert_eq!(unsafe { CStr::from_ptr(token.value) }.to_str(), Ok("a"));
+        assert_eq!(
+            unsafe { CStr::from_ptr(token.source_location.file_name) }.to_str(),
+            Ok("test.vn")
+        );
+        assert_eq!(array.get(6).unwrap().token_type, TokenType::Unknown);
+        assert_eq!(array.get(7).unwrap().token_type, TokenType::Eoft);
+        unsafe { vndr_token_array_free(array) };
+    }
+
+    fn token_types(array: &TokenDynamicArray) -> Vec<TokenType> {
+        (0..array.len())
+            .map(|index| array.get(index).unwrap().token_type.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_bad_escape_is_one_string_token() {
+        let array = tokenize("s = \"x\\qy\"");
+        assert_eq!(
+            token_types(&array),
+            [
+                TokenType::Identifier,
+                TokenType::Equal,
+                TokenType::String,
+                TokenType::Eoft
+            ]
+        );
+        unsafe { vndr_token_array_free(array) };
+    }
+
+    #[test]
+    fn test_unterminated_comment_is_one_comment_token() {
+        let array = tokenize("a /* open");
+        assert_eq!(
+            token_types(&array),
+            [TokenType::Identifier, TokenType::Comment, TokenType::Eoft]
+        );
+        unsafe { vndr_token_array_free(array) };
+    }
+
+    #[test]
+    fn test_free_single_token_then_array() {
+        let mut array = tokenize("a b");
+        unsafe { vndr_token_free(array.get_mut(0).unwrap()) };
+        assert!(array.get(0).unwrap().value.is_null());
+        unsafe { vndr_token_array_free(array) };
+    }
+
+    #[test]
+    fn test_free_empty_array() {
+        unsafe { vndr_token_array_free(TokenDynamicArray::new()) };
+    }
+
+    #[test]
+    fn test_tokenize_w_frees_everything() {
+        let file_name = CString::new("test.vn").unwrap();
+        let inputs: Vec<CString> = [
+            "var a: i8 = 3 @",
+            "s = \"x\\qy\" + 'c'",
+            "a /* open",
+            "fun f(x: f64[]): f64 {\n\treturn x[0] * 1.5e3i\n}",
+            "",
+        ]
+        .into_iter()
+        .map(|input| CString::new(input).unwrap())
+        .collect();
+        let tokenize_and_free = || {
+            for input in &inputs {
+                let mut array = unsafe { tokenize_w(file_name.as_ptr(), input.as_ptr()) };
+                if let Some(token) = array.get_mut(0) {
+                    unsafe { vndr_token_free(token) };
+                }
+                unsafe { vndr_token_array_free(array) };
+            }
+        };
+        // The first round may set up state that lives for the whole run.
+        tokenize_and_free();
+        let before = live_bytes();
+        for _ in 0..10 {
+            tokenize_and_free();
+        }
+        assert_eq!(live_bytes(), before);
+    }
+}
@@ -0,0 +1,307 @@
+use std::borrow::Cow;
+
+use crate::token::{Token, TokenType};
+
+/// Turns a token stream back into Vandior source with canonical spacing.
+///
+/// Statements are separated by newlines in Vandior, so lines are broken where the token structure
+/// ends a statement: inside and after the braces of a block, after a `//` comment and between
+/// an operand and a token that starts a new statement. Tokens built by a tool, which may all sit
+/// on one line, come out as valid source this way. A token on a later `source_location.line`
+/// than the end of the previous one also starts a new line, so breaks the structure cannot see,
+/// such as a statement starting with a sign, are kept, along with at most one blank line.
+/// Within a line tokens get canonical spacing, and lines are indented with one tab per open `{`.
+/// Strings and chars get their quotes back and `Eoft` is dropped.
+pub fn detokenize(tokens: &[Token<'_>]) -> String {
+    let mut output = String::new();
+    // For each open `{`, whether it opens a block rather than an array literal.
+    let mut braces: Vec<bool> = Vec::new();
+    let mut previous: Option<(&Token, bool)> = None;
+    let mut before_previous: Option<&Token> = None;
+    for token in tokens
+        .iter()
+        .filter(|token| token.token_type != TokenType::Eoft)
+    {
+        let is_block_brace = match token.token_type {
+            TokenType::OpenCurParenthesis => opens_block(previous.map(|(previous, _)| previous)),
+            TokenType::CloseCurParenthesis => braces.pop().unwrap_or(false),
+            _ => false,
+        };
+        if let Some((previous, previous_is_block_brace)) = previous {
+            let previous_line = previous.source_location.end_line;
+            if token.source_location.line > previous_line
+                || needs_line_break(previous, previous_is_block_brace, token, is_block_brace)
+            {
+                output.push('\n');
+                if token.source_location.line > previous_line + 1 {
+                    output.push('\n');
+                }
+                output.push_str(&"\t".repeat(braces.len()));
+            } else if needs_space(before_previous, previous, token) {
+                output.push(' ');
+            }
+        }
+        output.push_str(&source_text(token));
+        if token.token_type == TokenType::OpenCurParenthesis {
+            braces.push(is_block_brace);
+        }
+        // Signs are told apart by the code token before them, never by a comment.
+        if let Some((previous, _)) = previous.filter(|(previous, _)| !is_comment(previous)) {
+            before_previous = Some(previous);
+        }
+        previous = Some((token, is_block_brace));
+    }
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+fn is_comment(token: &Token) -> bool {
+    matches!(token.token_type, TokenType::Comment | TokenType::DocComment)
+}
+
+/// Whether a `{` following `before` opens a block; after an operator, an open bracket or
+/// `return` it starts an array literal instead.
+fn opens_block(before: Option<&Token>) -> bool {
+    use TokenType::*;
+    before.is_some_and(|before| {
+        !before.token_type.is_operator()
+            && !matches!(
+                before.token_type,
+                OpenParenthesis | OpenSqParenthesis | OpenCurParenthesis | KReturn
+            )
+    })
+}
+
+/// Whether `token` can only come after `previous` on a new line.
+fn needs_line_break(
+    previous: &Token,
+    previous_is_block_brace: bool,
+    token: &Token,
+    is_block_brace: bool,
+) -> bool {
+    use TokenType::*;
+    match (&previous.token_type, &token.token_type) {
+        (Comment | DocComment, _) => previous.value.starts_with("//"),
+        // An empty block stays `{}`.
+        (OpenCurParenthesis, CloseCurParenthesis) if previous_is_block_brace => false,
+        (OpenCurParenthesis, _) if previous_is_block_brace => true,
+        (_, CloseCurParenthesis) if is_block_brace => true,
+        (CloseCurParenthesis, KElse) => false,
+        (CloseCurParenthesis, _) if previous_is_block_brace => true,
+        _ => ends_operand(previous) && starts_statement(token),
+    }
+}
+
+/// Tokens after which a statement can be complete.
+fn ends_operand(token: &Token) -> bool {
+    use TokenType::*;
+    matches!(
+        token.token_type,
+        Identifier
+            | Integer
+            | Double
+            | Boolean
+            | Char
+            | String
+            | KNullptr
+            | KBreak
+            | KContinue
+            | CloseParenthesis
+            | CloseSqParenthesis
+            | CloseCurParenthesis
+            | PlusPlus
+            | MinusMinus
+    ) || token.token_type.is_type_keyword()
+}
+
+/// Tokens that cannot continue the expression before them, so they start a statement.
+fn starts_statement(token: &Token) -> bool {
+    use TokenType::*;
+    matches!(
+        token.token_type,
+        Identifier
+            | Integer
+            | Double
+            | Boolean
+            | Char
+            | String
+            | KNullptr
+            | KMain
+            | KVar
+            | KConst
+            | KIf
+            | KWhile
+            | KFor
+            | KBreak
+            | KContinue
+            | KFun
+            | KReturn
+    )
+}
+
+/// Text of a token as it is written in the source.
+fn source_text<'a>(token: &Token<'a>) -> Cow<'a, str> {
+    match token.token_type {
+        TokenType::String => Cow::Owned(format!("\"{}\"", token.value)),
+        TokenType::Char => Cow::Owned(format!("'{}'", token.value)),
+        _ => Cow::Borrowed(token.value),
+    }
+}
+
+/// Whether a `+` or `-` following `before` is a sign rather than a binary operator.
+fn is_sign(before: Option<&Token>) -> bool {
+    use TokenType::*;
+    !before.is_some_and(|before| {
+        matches!(
+            before.token_type,
+            Identifier
+                | Integer
+                | Double
+                | Boolean
+                | Char
+                | String
+                | KNullptr
+                | CloseParenthesis
+                | CloseSqParenthesis
+                | CloseCurParenthesis
+                | PlusPlus
+                | MinusMinus
+        )
+    })
+}
+
+fn needs_space(before_previous: Option<&Token>, previous: &Token, next: &Token) -> bool {
+    use TokenType::*;
+    if matches!(
+        previous.token_type,
+        OpenParenthesis | OpenSqParenthesis | OpenCurParenthesis | Dot | Not
+    ) {
+        return false;
+    }
+    if matches!(
+        next.token_type,
+        CloseParenthesis
+            | CloseSqParenthesis
+            | CloseCurParenthesis
+            | Comma
+            | Colon
+            | Dot
+            | PlusPlus
+            | MinusMinus
+    ) {
+        return false;
+    }
+    // Calls, indexing and array types: `f(x)`, `a[0]`, `i8[2]`.
+    if matches!(next.token_type, OpenParenthesis | OpenSqParenthesis)
+        && (matches!(
+            previous.token_type,
+            Identifier | CloseParenthesis | CloseSqParenthesis
+        ) || previous.token_type.is_type_keyword())
+    {
+        return false;
+    }
+    // `- -a` must not print as `--a`, which lexes as `MinusMinus`.
+    if matches!(previous.token_type, Plus | Minus) && matches!(next.token_type, Plus | Minus) {
+        return true;
+    }
+    !(matches!(previous.token_type, Plus | Minus) && is_sign(before_previous))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Tokenizer;
+
+    fn canonical(input: &str) -> String {
+        let (tokens, errors) = Tokenizer::new("test.vn", input).tokenize();
+        assert!(errors.is_empty());
+        detokenize(&tokens)
+    }
+
+    #[test]
+    fn test_canonical_spacing() {
+        assert_eq!(
+            canonical("fun  f( a :i8[ ][2] ) : i8 ,bool{\n  var s:string=\"a\\tb\"+'c'\n\n\n    return -a , ! s . size ( ) [0]\n}"),
+            "fun f(a: i8[][2]): i8, bool {\n\tvar s: string = \"a\\tb\" + 'c'\n\n\treturn -a, !s.size()[0]\n}\n"
+        );
+        assert_eq!(canonical("a = b - -1\ni++"), "a = b - -1\ni++\n");
+        assert_eq!(canonical("x = - -a"), "x = - -a\n");
+        assert_eq!(canonical("x = + +a"), "x = + +a\n");
+        assert_eq!(canonical("x = 1 /* c */ + 2"), "x = 1 /* c */ + 2\n");
+        assert_eq!(canonical("x = /* c */ -1"), "x = /* c */ -1\n");
+        assert_eq!(canonical("return {1,2} // done"), "return {1, 2} // done\n");
+    }
+
+    #[test]
+    fn test_tokens_on_one_line() {
+        use crate::token::{CodeSourceLocation, FileId};
+        use TokenType::*;
+        let location = CodeSourceLocation::new(FileId::intern("tool.vn"), 1, 0);
+        let tokens: Vec<Token> = [
+            (KFun, "fun"),
+            (Identifier, "f"),
+            (OpenParenthesis, "("),
+            (CloseParenthesis, ")"),
+            (OpenCurParenthesis, "{"),
+            (KVar, "var"),
+            (Identifier, "a"),
+            (Colon, ":"),
+            (TypeI8, "i8"),
+            (Identifier, "a"),
+            (Equal, "="),
+            (OpenCurParenthesis, "{"),
+            (Integer, "1"),
+            (CloseCurParenthesis, "}"),
+            (Comment, "// set"),
+            (KIf, "if"),
+            (Identifier, "a"),
+            (OpenCurParenthesis, "{"),
+            (CloseCurParenthesis, "}"),
+            (KElse, "else"),
+            (OpenCurParenthesis, "{"),
+            (KReturn, "return"),
+            (CloseCurParenthesis, "}"),
+            (CloseCurParenthesis, "}"),
+            (KMain, "main"),
+            (OpenCurParenthesis, "{"),
+            (Identifier, "f"),
+            (OpenParenthesis, "("),
+            (CloseParenthesis, ")"),
+            (CloseCurParenthesis, "}"),
+        ]
+        .into_iter()
+        .map(|(token_type, value)| Token::new(token_type, value, location))
+        .collect();
+        let printed = detokenize(&tokens);
+        assert_eq!(
+            printed,
+            "fun f() {\n\tvar a: i8\n\ta = {1} // set\n\tif a {} else {\n\t\treturn\n\t}\n}\nmain {\n\tf()\n}\n"
+        );
+        assert_eq!(canonical(&printed), printed);
+    }
+
+    #[test]
+    fn test_round_trip_input_file() {
+        let input = include_str!("../../../input.vn");
+        let (tokens, _) = Tokenizer::new("input.vn", input).tokenize();
+        let printed = detokenize(&tokens);
+        let (reparsed, errors) = Tokenizer::new("input.vn", &printed).tokenize();
+        assert!(errors.is_empty());
+        let summary = |tokens: &[Token]| -> Vec<(TokenType, String)> {
+            tokens
+                .iter()
+                .map(|token| (token.token_type.clone(), token.value.to_string()))
+                .collect()
+        };
+        assert_eq!(summary(&reparsed), summary(&tokens));
+        assert_eq!(detokenize(&reparsed), printed);
+    }
+
+    #[test]
+    fn test_empty_stream() {
+        assert_eq!(detokenize(&[]), "");
+        assert_eq!(canonical(""), "");
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+
+use crate::diagnostic::Diagnostic;
+use crate::token::{CodeSourceLocation, Span, Token, TokenType};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LexErrorKind {
+    #[default]
+    UnexpectedChar,
+    UnterminatedString,
+    UnterminatedChar,
+    UnterminatedBlockComment,
+    BadEscape,
+    MalformedNumber,
+    NumberOverflow,
+    InvalidNumberSuffix,
+}
+
+impl LexErrorKind {
+    pub fn message(&self) -> &'static str {
+        match self {
+            LexErrorKind::UnexpectedChar => "unexpected character",
+            LexErrorKind::UnterminatedString => "unterminated string literal",
+            LexErrorKind::UnterminatedChar => "unterminated char literal",
+            LexErrorKind::UnterminatedBlockComment => "unterminated block comment",
+            LexErrorKind::BadEscape => "invalid escape sequence",
+            LexErrorKind::MalformedNumber => "malformed number literal",
+            LexErrorKind::NumberOverflow => "number literal out of range",
+            LexErrorKind::InvalidNumberSuffix => "invalid number literal suffix",
+        }
+    }
+
+    /// Stable code shown by rendered diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexErrorKind::UnexpectedChar => "L0001",
+            LexErrorKind::UnterminatedString => "L0002",
+            LexErrorKind::UnterminatedChar => "L0003",
+            LexErrorKind::UnterminatedBlockComment => "L0004",
+            LexErrorKind::BadEscape => "L0005",
+            LexErrorKind::MalformedNumber => "L0006",
+            LexErrorKind::NumberOverflow => "L0007",
+            LexErrorKind::InvalidNumberSuffix => "L0008",
+        }
+    }
+
+    pub fn help(&self) -> Option<&'static str> {
+        match self {
+            LexErrorKind::UnterminatedString => Some("add a closing `\"`"),
+            LexErrorKind::UnterminatedChar => Some("add a closing `'`"),
+            LexErrorKind::UnterminatedBlockComment => {
+                Some("close the comment with `*/`; block comments can be nested")
+            }
+            LexErrorKind::BadEscape => Some(
+                "valid escapes are `\\n`, `\\t`, `\\r`, `\\\\`, `\\\"`, `\\'`, `\\0`, `\\xHH` and `\\u{H..}`",
+            ),
+            LexErrorKind::NumberOverflow => {
+                Some("integers must fit in 64 bits, `f32` floats in 32 bits")
+            }
+            LexErrorKind::InvalidNumberSuffix => {
+                Some("valid suffixes are `i`, `f` and `if`")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LexErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LexError<'src> {
+    pub kind: LexErrorKind,
+    pub span: Span,
+    pub lexeme: &'src str,
+    pub source_location: CodeSourceLocation,
+}
+
+impl<'src> LexError<'src> {
+    pub fn new(
+        kind: LexErrorKind,
+        span: Span,
+        lexeme: &'src str,
+        source_location: CodeSourceLocation,
+    ) -> Self {
+        Self {
+            kind,
+            span,
+            lexeme,
+            source_location,
+        }
+    }
+
+    /// The offending text as an `Unknown` token, for streams that must not lose any input.
+    pub fn to_token(&self) -> Token<'src> {
+        Token::new_with_span(
+            TokenType::Unknown,
+            self.lexeme,
+            self.span,
+            self.source_location,
+        )
+    }
+}
+
+impl LexError<'_> {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let label = match self.kind {
+            LexErrorKind::UnterminatedBlockComment => "this `/*` is never closed".to_string(),
+            _ => format!("'{}'", self.lexeme.escape_debug()),
+        };
+        let diagnostic = Diagnostic::error(self.source_location.file_id, self.kind.message())
+            .with_code(self.kind.code())
+            .with_primary_label(self.span, label);
+        match self.kind.help() {
+            Some(help) => diagnostic.with_help(help),
+            None => diagnostic,
+        }
+    }
+}
+
+impl fmt::Display for LexError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' at {}",
+            self.kind,
+            self.lexeme.escape_debug(),
+            self.source_location
+        )
+    }
+}
+
+impl std::error::Error for LexError<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::FileId;
+
+    #[test]
+    fn test_default_kind_is_unexpected_char() {
+        assert_eq!(LexErrorKind::default(), LexErrorKind::UnexpectedChar);
+    }
+
+    #[test]
+    fn test_to_diagnostic() {
+        let error = LexError::new(
+            LexErrorKind::BadEscape,
+            Span::new(3, 5),
+            "\\q",
+            CodeSourceLocation::new(FileId::intern("main.vn"), 1, 3),
+        );
+        let diagnostic = error.to_diagnostic();
+        assert_eq!(diagnostic.code.as_deref(), Some("L0005"));
+        assert_eq!(diagnostic.primary_span(), Some(Span::new(3, 5)));
+        assert_eq!(diagnostic.help.len(), 1);
+        assert_eq!(
+            format!("{}", diagnostic),
+            "error[L0005]: invalid escape sequence"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let error = LexError::new(
+            LexErrorKind::UnexpectedChar,
+            Span::new(4, 5),
+            "@",
+            CodeSourceLocation::new(FileId::intern("main.vn"), 1, 4),
+        );
+        assert_eq!(
+            format!("{}", error),
+            "unexpected character '@' at (file: main.vn,line: 1, column: 4)"
+        );
+    }
+}
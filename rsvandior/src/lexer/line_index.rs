@@ -0,0 +1,154 @@
+use std::ops::Range;
+
+/// Maps byte offsets of a source to `(line, column)` pairs and back.
+///
+/// Lines are 1-based and columns are 0-based counts of chars, the same convention used by
+/// `CodeSourceLocation`. Both directions are answered with a binary search over the line starts
+/// and over the multibyte chars of the source, so the index is built once and queried in
+/// O(log n).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+    multibyte_chars: Vec<usize>,
+    /// Char index in the whole source of each of `multibyte_chars`.
+    multibyte_char_indices: Vec<usize>,
+    extra_bytes: Vec<usize>,
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(input: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut multibyte_chars = Vec::new();
+        let mut multibyte_char_indices = Vec::new();
+        let mut extra_bytes = vec![0];
+        for (offset, c) in input.char_indices() {
+            if c == '\n' {
+                line_starts.push(offset + 1);
+            }
+            let char_len = c.len_utf8();
+            if char_len > 1 {
+                let extra = *extra_bytes.last().unwrap_or(&0);
+                multibyte_chars.push(offset);
+                multibyte_char_indices.push(offset - extra);
+                extra_bytes.push(extra + char_len - 1);
+            }
+        }
+        Self {
+            line_starts,
+            multibyte_chars,
+            multibyte_char_indices,
+            extra_bytes,
+            len: input.len(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the 1-based line and the 0-based char column of `offset`.
+    /// Offsets past the end of the source are clamped to its end.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = offset
+            - line_start
+            - (self.extra_bytes_before(offset) - self.extra_bytes_before(line_start));
+        (line, column)
+    }
+
+    /// Returns the byte offset of the 1-based `line` and 0-based char `column`, or `None` when
+    /// the position lies outside the source.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        let line_span = self.line_span(line)?;
+        let char_index = line_span.start - self.extra_bytes_before(line_span.start) + column;
+        let before = self
+            .multibyte_char_indices
+            .partition_point(|&index| index < char_index);
+        let offset = char_index + self.extra_bytes[before];
+        (offset <= line_span.end).then_some(offset)
+    }
+
+    /// Returns the byte range of the 1-based `line`, excluding its terminating newline.
+    pub fn line_span(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.len, |next_start| next_start - 1);
+        Some(start..end)
+    }
+
+    fn extra_bytes_before(&self, offset: usize) -> usize {
+        self.extra_bytes[self
+            .multibyte_chars
+            .partition_point(|&start| start < offset)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_col() {
+        let index = LineIndex::new("ab\ncd\n\nef");
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line_col(0), (1, 0));
+        assert_eq!(index.line_col(2), (1, 2));
+        assert_eq!(index.line_col(3), (2, 0));
+        assert_eq!(index.line_col(6), (3, 0));
+        assert_eq!(index.line_col(8), (4, 1));
+        assert_eq!(index.line_col(100), (4, 2));
+    }
+
+    #[test]
+    fn test_line_col_counts_chars() {
+        let index = LineIndex::new("é变x\nü y");
+        assert_eq!(index.line_col(5), (1, 2));
+        assert_eq!(index.line_col(7), (2, 0));
+        assert_eq!(index.line_col(10), (2, 2));
+    }
+
+    #[test]
+    fn test_offset_round_trip() {
+        let input = "var 变量: i8\n\té = \"ü\"\n";
+        let index = LineIndex::new(input);
+        for (offset, _) in input.char_indices() {
+            let (line, column) = index.line_col(offset);
+            assert_eq!(index.offset(line, column), Some(offset));
+        }
+        assert_eq!(index.offset(0, 0), None);
+        assert_eq!(index.offset(1, 50), None);
+        assert_eq!(index.offset(4, 0), None);
+    }
+
+    #[test]
+    fn test_offset_on_long_multibyte_line() {
+        let input = format!("x\n{}\ny", "é变".repeat(1000));
+        let index = LineIndex::new(&input);
+        assert_eq!(index.offset(2, 0), Some(2));
+        assert_eq!(index.offset(2, 1001), Some(2 + 500 * 5 + 2));
+        assert_eq!(index.offset(2, 2000), Some(2 + 5000));
+        assert_eq!(index.offset(2, 2001), None);
+        assert_eq!(index.offset(3, 0), Some(input.len() - 1));
+    }
+
+    #[test]
+    fn test_line_span() {
+        let index = LineIndex::new("ab\ncd");
+        assert_eq!(index.line_span(1), Some(0..2));
+        assert_eq!(index.line_span(2), Some(3..5));
+        assert_eq!(index.line_span(3), None);
+    }
+}
@@ -1,3 +1,9 @@
+pub mod detokenizer;
+pub mod lex_error;
+pub mod line_index;
 pub mod tokenizer;
 
+pub use detokenizer::*;
+pub use lex_error::*;
+pub use line_index::*;
 pub use tokenizer::*;
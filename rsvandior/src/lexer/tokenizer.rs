@@ -1,192 +1,675 @@
-use std::ffi::CStr;
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
 
-use crate::token::{CodeSourceLocation, RawToken, Token, TokenType};
+use crate::lexer::{LexError, LexErrorKind, LineIndex};
+use crate::token::{
+    doc_comment_body, scan_block_comment, unescape, CodeSourceLocation, FileId, LosslessToken,
+    NumericLiteral, RawToken, Span, Token, TokenType, Trivia, TriviaKind,
+};
 use logos::{Lexer, Logos};
 
-#[repr(C)]
-#[allow(dead_code)]
 pub struct Tokenizer<'a> {
     input: &'a str,
-    file_name: &'a str,
-    line: usize,
-    column: usize,
+    file_id: FileId,
+    line_index: LineIndex,
     lexer: Lexer<'a, RawToken>,
+    queued: VecDeque<Result<Token<'a>, LexError<'a>>>,
+    finished: bool,
 }
 
 impl<'a> Tokenizer<'a> {
     pub fn new(file_name: &'a str, input: &'a str) -> Self {
         Self {
-            file_name: file_name,
+            file_id: FileId::intern(file_name),
             input,
-            line: 0,
-            column: 0,
+            line_index: LineIndex::new(input),
             lexer: RawToken::lexer(input),
+            queued: VecDeque::new(),
+            finished: false,
         }
     }
 
-    fn get_line_and_column(&mut self) -> (usize, usize) {
-        let byte_index = self.lexer.span().start;
-        let lines: Vec<&str> = self.input[..byte_index].split('\n').collect();
-        let line_number = lines.len();
-        let column_number = lines.last().unwrap_or(&"").chars().count();
-        (line_number, column_number)
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
     }
 
-    pub fn tokenize(&mut self) -> Vec<Token> {
+    fn source_location(&self, span: Span) -> CodeSourceLocation {
+        let (line, column) = self.line_index.line_col(span.start);
+        let (end_line, end_column) = self.line_index.line_col(span.end);
+        CodeSourceLocation::new_with_end(self.file_id, line, column, end_line, end_column)
+    }
+
+    /// Returns the next item without consuming it.
+    pub fn peek(&mut self) -> Option<&Result<Token<'a>, LexError<'a>>> {
+        if self.queued.is_empty() {
+            if let Some(item) = self.lex_next() {
+                self.queued.push_front(item);
+            }
+        }
+        self.queued.front()
+    }
+
+    fn lex_next(&mut self) -> Option<Result<Token<'a>, LexError<'a>>> {
+        if self.finished {
+            return None;
+        }
+        let Some(raw_token) = self.lexer.next() else {
+            self.finished = true;
+            let eof_span = Span::new(self.input.len(), self.input.len());
+            return Some(Ok(Token::new_with_span(
+                TokenType::Eoft,
+                "",
+                eof_span,
+                self.source_location(eof_span),
+            )));
+        };
+        let span = Span::from(self.lexer.span());
+        let value = self.lexer.slice();
+        let source_location = self.source_location(span);
+        Some(match raw_token {
+            Ok(raw_token) => {
+                let (token_type, remapped_value) = TokenType::map_raw_token_type(raw_token, value);
+                if token_type == TokenType::DocComment {
+                    Ok(Token::new_with_cooked(
+                        token_type,
+                        value,
+                        span,
+                        source_location,
+                        doc_comment_body(value),
+                    ))
+                } else if matches!(token_type, TokenType::String | TokenType::Char) {
+                    Ok(self.cook_literal(token_type, remapped_value, span, source_location))
+                } else if matches!(token_type, TokenType::Integer | TokenType::Double) {
+                    NumericLiteral::parse(value)
+                        .map(|numeric| {
+                            Token::new_with_numeric(
+                                token_type,
+                                remapped_value,
+                                span,
+                                source_location,
+                                numeric,
+                            )
+                        })
+                        .map_err(|kind| LexError::new(kind, span, value, source_location))
+                } else {
+                    Ok(Token::new_with_span(
+                        token_type,
+                        remapped_value,
+                        span,
+                        source_location,
+                    ))
+                }
+            }
+            Err(LexErrorKind::UnterminatedBlockComment) => {
+                // The comment stays in the stream; the error points at the opener left unclosed.
+                let opener = span.start + scan_block_comment(value).unwrap_err();
+                let opener_span = Span::new(opener, opener + 2);
+                self.queued.push_back(Err(LexError::new(
+                    LexErrorKind::UnterminatedBlockComment,
+                    opener_span,
+                    opener_span.slice(self.input),
+                    self.source_location(opener_span),
+                )));
+                Ok(Token::new_with_span(
+                    TokenType::Comment,
+                    value,
+                    span,
+                    source_location,
+                ))
+            }
+            Err(kind) => Err(LexError::new(kind, span, value, source_location)),
+        })
+    }
+
+    /// Decodes the escapes of a string or char literal. Every bad escape is queued as an error
+    /// right after the token, which is still produced, without a cooked value.
+    fn cook_literal(
+        &mut self,
+        token_type: TokenType,
+        body: &'a str,
+        span: Span,
+        source_location: CodeSourceLocation,
+    ) -> Token<'a> {
+        match unescape(body) {
+            Ok(cooked) => Token::new_with_cooked(token_type, body, span, source_location, cooked),
+            Err(errors) => {
+                // The body starts after the opening quote.
+                let body_start = span.start + 1;
+                for error in errors {
+                    let error_span =
+                        Span::new(body_start + error.span.start, body_start + error.span.end);
+                    self.queued.push_back(Err(LexError::new(
+                        error.kind,
+                        error_span,
+                        error_span.slice(self.input),
+                        self.source_location(error_span),
+                    )));
+                }
+                Token::new_with_span(token_type, body, span, source_location)
+            }
+        }
+    }
+
+    pub fn tokenize(&mut self) -> (Vec<Token<'a>>, Vec<LexError<'a>>) {
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut errors: Vec<LexError> = Vec::new();
+        for item in self.by_ref() {
+            match item {
+                Ok(token) => tokens.push(token),
+                Err(error) => errors.push(error),
+            }
+        }
+        (tokens, errors)
+    }
+
+    pub fn tokenize_lossless(&mut self) -> (Vec<LosslessToken<'a>>, Vec<LexError<'a>>) {
         let mut tokens: Vec<Token> = Vec::new();
-        while let Some(raw_token) = self.lexer.next() {
-            //let span = lexer.span();
-            let value = self.lexer.clone().slice();
-            let (line, column) = self.get_line_and_column();
-            let source_location = CodeSourceLocation::new(self.file_name.to_string(), line, column);
-            let (token_type, remapped_value) =
-                TokenType::map_raw_token_type(raw_token.clone().unwrap(), value.to_string());
-            tokens.push(Token::new(
-                token_type,
-                remapped_value.to_string(),
-                source_location,
+        let mut errors: Vec<LexError> = Vec::new();
+        for item in self.by_ref() {
+            match item {
+                Ok(token) => tokens.push(token),
+                Err(error) => {
+                    // Errors inside a token that is already in the stream need no stand-in.
+                    if tokens
+                        .last()
+                        .is_none_or(|token| token.span.end <= error.span.start)
+                    {
+                        tokens.push(error.to_token());
+                    }
+                    errors.push(error);
+                }
+            }
+        }
+
+        let mut lossless_tokens: Vec<LosslessToken> = Vec::new();
+        let mut pending: Vec<Trivia> = Vec::new();
+        let mut cursor = 0;
+        for token in tokens {
+            self.push_whitespace_trivia(cursor, token.span.start, &mut pending);
+            cursor = token.span.end;
+            let text = token.span.slice(self.input);
+            if token.token_type == TokenType::Comment {
+                let kind = if text.starts_with("//") {
+                    TriviaKind::LineComment
+                } else {
+                    TriviaKind::BlockComment
+                };
+                pending.push(Trivia::new(kind, token.span, text));
+                continue;
+            }
+            if let Some(previous) = lossless_tokens.last_mut() {
+                let trailing_len = pending
+                    .iter()
+                    .position(Trivia::is_newline)
+                    .map_or(pending.len(), |index| index + 1);
+                previous.trailing_trivia = pending.drain(..trailing_len).collect();
+            }
+            lossless_tokens.push(LosslessToken::new(
+                token,
+                text,
+                std::mem::take(&mut pending),
+                Vec::new(),
+            ));
+        }
+        (lossless_tokens, errors)
+    }
+
+    fn push_whitespace_trivia(&self, start: usize, end: usize, trivia: &mut Vec<Trivia<'a>>) {
+        let mut run_start = start;
+        for (offset, c) in self.input[start..end].char_indices() {
+            if c == '\n' {
+                let newline_start = start + offset;
+                if run_start < newline_start {
+                    let span = Span::new(run_start, newline_start);
+                    trivia.push(Trivia::new(
+                        TriviaKind::Whitespace,
+                        span,
+                        span.slice(self.input),
+                    ));
+                }
+                let span = Span::new(newline_start, newline_start + 1);
+                trivia.push(Trivia::new(
+                    TriviaKind::Newline,
+                    span,
+                    &self.input[newline_start..newline_start + 1],
+                ));
+                run_start = newline_start + 1;
+            }
+        }
+        if run_start < end {
+            let span = Span::new(run_start, end);
+            trivia.push(Trivia::new(
+                TriviaKind::Whitespace,
+                span,
+                span.slice(self.input),
             ));
-            self.line = line;
-            self.column = column + 1;
         }
-        tokens.push(Token::new(
-            TokenType::Eoft,
-            "".to_string(),
-            CodeSourceLocation::new(self.file_name.to_string(), self.line, self.column),
-        ));
-        tokens
     }
 }
 
-use std::alloc::{alloc, dealloc, realloc, Layout};
-use std::ptr;
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Token<'a>, LexError<'a>>;
 
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct TokenDynamicArray {
-    ptr: *mut Token, // Puntatore all'array allocato
-    capacity: usize, // Capacità totale dell'array
-    size: usize,     // Numero di elementi nell'array
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queued.pop_front().or_else(|| self.lex_next())
+    }
 }
 
-impl TokenDynamicArray {
-    pub fn new() -> Self {
-        TokenDynamicArray {
-            ptr: ptr::null_mut(),
-            capacity: 0,
-            size: 0,
-        }
+impl FusedIterator for Tokenizer<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_kinds(input: &str) -> Vec<LexErrorKind> {
+        let (_, errors) = Tokenizer::new("test.vn", input).tokenize();
+        errors.into_iter().map(|error| error.kind).collect()
     }
 
-    pub fn with_capacity(capacity: usize) -> Self {
-        let layout = Layout::array::<Token>(capacity).expect("Layout creation failed");
-        let ptr = unsafe { alloc(layout) as *mut Token };
-        TokenDynamicArray {
-            ptr,
-            capacity,
-            size: 0,
+    #[test]
+    fn test_tokenize_input_without_errors() {
+        let (tokens, errors) = Tokenizer::new("test.vn", "var a: i8 = 3").tokenize();
+        assert!(errors.is_empty());
+        assert_eq!(tokens.len(), 7);
+        assert_eq!(tokens.last().unwrap().token_type, TokenType::Eoft);
+    }
+
+    #[test]
+    fn test_unexpected_char_is_recovered() {
+        let (tokens, errors) = Tokenizer::new("test.vn", "a @ b $ c").tokenize();
+        let types: Vec<TokenType> = tokens.iter().map(|t| t.token_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::Identifier,
+                TokenType::Identifier,
+                TokenType::Identifier,
+                TokenType::Eoft
+            ]
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].kind, LexErrorKind::UnexpectedChar);
+        assert_eq!(errors[0].span, Span::new(2, 3));
+        assert_eq!(errors[0].lexeme, "@");
+        assert_eq!(errors[0].source_location.column, 2);
+        assert_eq!(errors[1].lexeme, "$");
+    }
+
+    #[test]
+    fn test_tokens_carry_spans_and_end_positions() {
+        let input = "var name: i8\n  = \"ab\"";
+        let (tokens, _) = Tokenizer::new("test.vn", input).tokenize();
+        let name = &tokens[1];
+        assert_eq!(name.span, Span::new(4, 8));
+        assert_eq!(name.span.slice(input), "name");
+        assert_eq!(name.source_location.line, 1);
+        assert_eq!(name.source_location.column, 4);
+        assert_eq!(name.source_location.end_line, 1);
+        assert_eq!(name.source_location.end_column, 8);
+        let string = &tokens[5];
+        assert_eq!(string.span.slice(input), "\"ab\"");
+        assert_eq!(string.source_location.line, 2);
+        assert_eq!(string.source_location.column, 4);
+        assert_eq!(string.source_location.end_column, 8);
+        let eof = tokens.last().unwrap();
+        assert_eq!(eof.span, Span::new(input.len(), input.len()));
+        assert_eq!(eof.source_location.line, 2);
+    }
+
+    #[test]
+    fn test_tokenize_lossless_reproduces_input() {
+        let inputs = [
+            include_str!("../../../input.vn"),
+            "",
+            "  \n// only a comment",
+            "a @ b /* c */\n\t\"unterminated",
+        ];
+        for input in inputs {
+            let (tokens, _) = Tokenizer::new("test.vn", input).tokenize_lossless();
+            assert_eq!(LosslessToken::to_full_string(&tokens), input);
+            assert_eq!(tokens.last().unwrap().token.token_type, TokenType::Eoft);
         }
     }
 
-    pub fn push(&mut self, item: Token) {
-        if self.size == self.capacity {
-            self.resize();
+    #[test]
+    fn test_tokenize_lossless_attaches_trivia() {
+        let input = "a = 1 // one\n  /* two */ b";
+        let (tokens, errors) = Tokenizer::new("test.vn", input).tokenize_lossless();
+        assert!(errors.is_empty());
+        assert_eq!(tokens.len(), 5);
+        let one = &tokens[2];
+        assert_eq!(one.text, "1");
+        let trailing: Vec<TriviaKind> = one.trailing_trivia.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            trailing,
+            vec![
+                TriviaKind::Whitespace,
+                TriviaKind::LineComment,
+                TriviaKind::Newline
+            ]
+        );
+        let b = &tokens[3];
+        let leading: Vec<TriviaKind> = b.leading_trivia.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            leading,
+            vec![
+                TriviaKind::Whitespace,
+                TriviaKind::BlockComment,
+                TriviaKind::Whitespace
+            ]
+        );
+        assert_eq!(b.leading_trivia[1].text, "/* two */");
+    }
+
+    #[test]
+    fn test_iterator_yields_tokens_errors_and_eof() {
+        let mut tokenizer = Tokenizer::new("test.vn", "a @ 1");
+        assert_eq!(
+            tokenizer.next().unwrap().unwrap().token_type,
+            TokenType::Identifier
+        );
+        assert_eq!(
+            tokenizer.next().unwrap().unwrap_err().kind,
+            LexErrorKind::UnexpectedChar
+        );
+        assert_eq!(
+            tokenizer.next().unwrap().unwrap().token_type,
+            TokenType::Integer
+        );
+        assert_eq!(
+            tokenizer.next().unwrap().unwrap().token_type,
+            TokenType::Eoft
+        );
+        assert!(tokenizer.next().is_none());
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn test_peek_does_not_consume() {
+        let mut tokenizer = Tokenizer::new("test.vn", "a 1");
+        let peeked = tokenizer
+            .peek()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .token_type
+            .clone();
+        assert_eq!(peeked, TokenType::Identifier);
+        assert!(tokenizer.peek().is_some());
+        assert_eq!(tokenizer.next().unwrap().unwrap().token_type, peeked);
+        assert_eq!(
+            tokenizer.peek().unwrap().as_ref().unwrap().token_type,
+            TokenType::Integer
+        );
+        assert_eq!(tokenizer.count(), 2);
+    }
+
+    #[test]
+    fn test_every_operator_is_reachable() {
+        let spellings = [
+            ("+", TokenType::Plus),
+            ("-", TokenType::Minus),
+            ("!", TokenType::Not),
+            ("*", TokenType::Star),
+            ("/", TokenType::Divide),
+            ("^", TokenType::Xor),
+            ("%", TokenType::Percent),
+            ("|", TokenType::Or),
+            ("&", TokenType::And),
+            ("=", TokenType::Equal),
+            ("<", TokenType::Less),
+            (">", TokenType::Greater),
+            ("++", TokenType::PlusPlus),
+            ("--", TokenType::MinusMinus),
+            ("+=", TokenType::PlusEqual),
+            ("-=", TokenType::MinusEqual),
+            ("!=", TokenType::NotEqual),
+            ("*=", TokenType::StarEqual),
+            ("/=", TokenType::DivideEqual),
+            ("^=", TokenType::XorEqual),
+            ("%=", TokenType::PercentEqual),
+            ("||", TokenType::OrOr),
+            ("&&", TokenType::AndAnd),
+            ("==", TokenType::EqualEqual),
+            ("<=", TokenType::LessEqual),
+            (">=", TokenType::GreaterEqual),
+            ("|=", TokenType::OrEqual),
+            ("&=", TokenType::AndEqual),
+            ("<<", TokenType::ShiftLeft),
+            (">>", TokenType::ShiftRight),
+            ("->", TokenType::Arrow),
+            (".", TokenType::Dot),
+            (",", TokenType::Comma),
+            (":", TokenType::Colon),
+        ];
+        for (spelling, token_type) in &spellings {
+            let input = format!("a {} b", spelling);
+            let (tokens, errors) = Tokenizer::new("test.vn", &input).tokenize();
+            assert!(errors.is_empty(), "{}", spelling);
+            assert_eq!(tokens.len(), 4, "{}", spelling);
+            assert_eq!(&tokens[1].token_type, token_type, "{}", spelling);
+            assert_eq!(tokens[1].value, *spelling);
         }
-        unsafe {
-            ptr::write(self.ptr.add(self.size), item);
+        for operator in TokenType::OPERATORS {
+            assert!(
+                spellings
+                    .iter()
+                    .any(|(_, token_type)| *token_type == operator),
+                "{:?} is not produced by any input",
+                operator
+            );
         }
-        self.size += 1;
     }
 
-    fn resize(&mut self) {
-        let new_capacity = if self.capacity == 0 {
-            1
-        } else {
-            self.capacity * 2
-        };
-        let new_layout = Layout::array::<Token>(new_capacity).expect("Layout creation failed");
-        let new_ptr = if self.capacity == 0 {
-            unsafe { alloc(new_layout) as *mut Token }
-        } else {
-            let old_layout = Layout::array::<Token>(self.capacity).expect("Layout creation failed");
-            unsafe { realloc(self.ptr as *mut u8, old_layout, new_layout.size()) as *mut Token }
+    #[test]
+    fn test_compound_operators_in_expressions() {
+        let types = |input: &str| -> Vec<TokenType> {
+            let (tokens, _) = Tokenizer::new("test.vn", input).tokenize();
+            tokens.into_iter().map(|token| token.token_type).collect()
         };
-        self.ptr = new_ptr;
-        self.capacity = new_capacity;
+        assert_eq!(
+            types("1 == 1"),
+            vec![
+                TokenType::Integer,
+                TokenType::EqualEqual,
+                TokenType::Integer,
+                TokenType::Eoft
+            ]
+        );
+        assert_eq!(
+            types("num1 -= num"),
+            vec![
+                TokenType::Identifier,
+                TokenType::MinusEqual,
+                TokenType::Identifier,
+                TokenType::Eoft
+            ]
+        );
     }
 
-    pub fn pop(&mut self) -> Option<Token> {
-        if self.size == 0 {
-            None
-        } else {
-            self.size -= 1;
-            unsafe { Some(ptr::read(self.ptr.add(self.size))) }
-        }
+    #[test]
+    fn test_extended_keywords() {
+        let (tokens, _) =
+            Tokenizer::new("test.vn", "const continue nullptr true struct").tokenize();
+        let types: Vec<TokenType> = tokens.into_iter().map(|token| token.token_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::KConst,
+                TokenType::KContinue,
+                TokenType::KNullptr,
+                TokenType::Boolean,
+                TokenType::KReserved,
+                TokenType::Eoft
+            ]
+        );
     }
 
-    pub fn get(&self, index: usize) -> Option<&Token> {
-        if index < self.size {
-            unsafe { Some(&*self.ptr.add(index)) }
-        } else {
-            None
-        }
+    #[test]
+    fn test_unterminated_literals() {
+        assert_eq!(
+            error_kinds("a = \"abc"),
+            vec![LexErrorKind::UnterminatedString]
+        );
+        assert_eq!(error_kinds("a = 'b"), vec![LexErrorKind::UnterminatedChar]);
+        assert_eq!(
+            error_kinds("a /* comment"),
+            vec![LexErrorKind::UnterminatedBlockComment]
+        );
     }
 
-    pub fn set(&mut self, index: usize, item: Token) {
-        if index < self.size {
-            unsafe {
-                ptr::write(self.ptr.add(index), item);
-            }
-        } else {
-            panic!("Index out of bounds");
-        }
+    #[test]
+    fn test_doc_comments() {
+        let input = "/// Adds two numbers.\n// plain\n/**\n * Returns the sum.\n */\nfun add";
+        let (tokens, errors) = Tokenizer::new("test.vn", input).tokenize();
+        assert!(errors.is_empty());
+        let types: Vec<TokenType> = tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::DocComment,
+                TokenType::Comment,
+                TokenType::DocComment,
+                TokenType::KFun,
+                TokenType::Identifier,
+                TokenType::Eoft
+            ]
+        );
+        assert_eq!(tokens[0].value, "/// Adds two numbers.");
+        assert_eq!(tokens[0].cooked.as_deref(), Some("Adds two numbers."));
+        assert_eq!(tokens[2].cooked.as_deref(), Some("Returns the sum."));
+
+        let (tokens, _) = Tokenizer::new("test.vn", input).tokenize_lossless();
+        assert_eq!(tokens[0].token.token_type, TokenType::DocComment);
+        assert_eq!(LosslessToken::to_full_string(&tokens), input);
     }
 
-    pub fn len(&self) -> usize {
-        self.size
+    #[test]
+    fn test_nested_block_comments() {
+        let input = "a /* outer /* inner */ still outer */ b";
+        let (tokens, errors) = Tokenizer::new("test.vn", input).tokenize();
+        assert!(errors.is_empty());
+        let types: Vec<TokenType> = tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::Identifier,
+                TokenType::Comment,
+                TokenType::Identifier,
+                TokenType::Eoft
+            ]
+        );
+        assert_eq!(tokens[1].value, "/* outer /* inner */ still outer */");
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.size == 0
+    #[test]
+    fn test_unterminated_block_comment_points_at_the_opener() {
+        let input = "a\n/* outer /* inner */\nb";
+        let (tokens, errors) = Tokenizer::new("test.vn", input).tokenize();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, Span::new(2, 4));
+        assert_eq!(errors[0].source_location.line, 2);
+        assert_eq!(errors[0].source_location.column, 0);
+        assert_eq!(tokens[1].token_type, TokenType::Comment);
+        assert_eq!(tokens[1].value, "/* outer /* inner */\nb");
+
+        let (_, errors) = Tokenizer::new("test.vn", "/* a /* b").tokenize();
+        assert_eq!(errors[0].span, Span::new(5, 7));
+
+        let (tokens, _) = Tokenizer::new("test.vn", input).tokenize_lossless();
+        assert_eq!(LosslessToken::to_full_string(&tokens), input);
     }
 
-    pub fn capacity(&self) -> usize {
-        self.capacity
+    #[test]
+    fn test_bad_escape() {
+        assert_eq!(error_kinds(r#""a\qb""#), vec![LexErrorKind::BadEscape]);
+        assert_eq!(error_kinds(r"'\q'"), vec![LexErrorKind::BadEscape]);
+        assert!(error_kinds(r#""a\tb" '\n'"#).is_empty());
     }
 
-    pub fn clear(&mut self) {
-        while self.pop().is_some() {}
+    #[test]
+    fn test_bad_escape_points_at_the_escape() {
+        let input = r#"println("Funzione:\t{} \q \u{zz}", a)"#;
+        let items: Vec<_> = Tokenizer::new("test.vn", input).collect();
+        let string = items[2].as_ref().unwrap();
+        assert_eq!(string.token_type, TokenType::String);
+        assert_eq!(string.cooked, None);
+        let errors: Vec<&LexError> = items
+            .iter()
+            .filter_map(|item| item.as_ref().err())
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].lexeme, r"\q");
+        assert_eq!(errors[0].span, Span::new(23, 25));
+        assert_eq!(errors[0].source_location.column, 23);
+        assert_eq!(errors[1].lexeme, r"\u{");
+        assert!(items[3].is_err() && items[4].is_err());
+
+        let (tokens, _) = Tokenizer::new("test.vn", input).tokenize_lossless();
+        assert_eq!(LosslessToken::to_full_string(&tokens), input);
     }
-}
 
-impl Drop for TokenDynamicArray {
-    fn drop(&mut self) {
-        if !self.ptr.is_null() {
-            self.clear();
-            let layout = Layout::array::<Token>(self.capacity).expect("Layout creation failed");
-            unsafe {
-                dealloc(self.ptr as *mut u8, layout);
-            }
-        }
+    #[test]
+    fn test_literals_are_cooked() {
+        let (tokens, errors) =
+            Tokenizer::new("test.vn", r#""Funzione:\t{}" '\x41' '\u{e8}' "plain""#).tokenize();
+        assert!(errors.is_empty());
+        let cooked: Vec<&str> = tokens
+            .iter()
+            .filter_map(|token| token.cooked.as_deref())
+            .collect();
+        assert_eq!(cooked, vec!["Funzione:\t{}", "A", "è", "plain"]);
+        assert_eq!(tokens[0].value, r"Funzione:\t{}");
+    }
+
+    #[test]
+    fn test_malformed_number() {
+        assert_eq!(error_kinds("12abc"), vec![LexErrorKind::MalformedNumber]);
+        assert_eq!(error_kinds("#o89"), vec![LexErrorKind::MalformedNumber]);
+        assert_eq!(error_kinds("##12"), vec![LexErrorKind::MalformedNumber]);
+        assert!(error_kinds("#o23 ##1010 #ff 1.5if 1e5 2f").is_empty());
+    }
+
+    #[test]
+    fn test_numeric_literals_are_decoded() {
+        let (tokens, errors) =
+            Tokenizer::new("test.vn", "#o3444 ##1010 #334affff 34.5i 2.3if").tokenize();
+        assert!(errors.is_empty());
+        let numerics: Vec<NumericLiteral> =
+            tokens.iter().filter_map(|token| token.numeric).collect();
+        assert_eq!(numerics.len(), 5);
+        assert_eq!(numerics[0].as_u64(), Some(0o3444));
+        assert_eq!(numerics[1].as_u64(), Some(0b1010));
+        assert_eq!(numerics[2].as_u64(), Some(0x334affff));
+        assert!(numerics[3].imaginary && !numerics[3].is_f32);
+        assert_eq!(numerics[3].as_f64(), 34.5);
+        assert!(numerics[4].imaginary && numerics[4].is_f32);
+        assert_eq!(tokens[4].token_type, TokenType::Double);
+    }
+
+    #[test]
+    fn test_numeric_literal_diagnostics() {
+        assert_eq!(
+            error_kinds("99999999999999999999 #10000000000000000 1e400"),
+            vec![LexErrorKind::NumberOverflow; 3]
+        );
+        assert_eq!(
+            error_kinds("1ff 2.0ii"),
+            vec![LexErrorKind::InvalidNumberSuffix; 2]
+        );
     }
-}
 
-#[no_mangle]
-pub extern "C" fn tokenize_w(
-    file_name: *const std::os::raw::c_char,
-    input: *const std::os::raw::c_char,
-) -> TokenDynamicArray {
-    let c_str1 = unsafe { CStr::from_ptr(file_name) };
-    let c_str2 = unsafe { CStr::from_ptr(input) };
-    let file_name_str = c_str1.to_str().unwrap_or("Unknown");
-    let input_str = c_str2.to_str().unwrap_or("Unknown");
-    let mut tokenizer = Tokenizer::new(file_name_str, input_str);
-
-    let tolens_vec = tokenizer.tokenize();
-    let mut tokens = TokenDynamicArray::with_capacity(tolens_vec.len());
-    tolens_vec.into_iter().for_each(|token| {
-        tokens.push(token);
-    });
-    tokens
-    //Box::new(tokenizer.tokenize())
+    #[test]
+    fn test_tokenize_input_file() {
+        let input = include_str!("../../../input.vn");
+        let (tokens, errors) = Tokenizer::new("input.vn", input).tokenize();
+        assert!(errors.is_empty());
+        assert!(tokens.len() > 1);
+    }
 }
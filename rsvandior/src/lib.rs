@@ -1,13 +1,6 @@
 
+pub mod diagnostic;
+pub mod ffi;
 pub mod lexer;
+pub mod parser;
 pub mod token;
-
-#[cfg(test)]
-mod tests {
-
-    #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
-    }
-}
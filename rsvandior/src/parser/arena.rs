@@ -0,0 +1,562 @@
+use std::borrow::Cow;
+use std::ops::Index;
+
+use super::ast::{BinaryOp, Expr, ExprKind, UnaryOp};
+use super::node_type::NodeType;
+use super::stmt::{Block, ElseBranch, Ident, Param, Stmt, StmtKind};
+use super::type_expr::{PrimitiveType, TypeExpr, TypeExprKind};
+use crate::token::{NumericLiteral, Span};
+
+/// Index of a node in an [`AstArena`]. Ids never change while the arena lives; they grow in
+/// post-order, so a node always comes before its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// The AST nodes as stored in the arena: the same shapes as [`StmtKind`], [`ExprKind`] and
+/// [`TypeExprKind`], with children referenced by id. `ElseBranch` has no node of its own: an
+/// `If` points straight at its `else if` statement or `else` block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind<'src> {
+    Declaration {
+        doc: Option<Cow<'src, str>>,
+        is_const: bool,
+        names: Vec<NodeId>,
+        ty: Option<NodeId>,
+        values: Vec<NodeId>,
+    },
+    Assignment {
+        targets: Vec<NodeId>,
+        op: BinaryOp,
+        values: Vec<NodeId>,
+    },
+    Expression(NodeId),
+    Main(NodeId),
+    Function {
+        doc: Option<Cow<'src, str>>,
+        name: NodeId,
+        params: Vec<NodeId>,
+        returns: Option<NodeId>,
+        body: NodeId,
+    },
+    If {
+        condition: NodeId,
+        then_block: NodeId,
+        else_branch: Option<NodeId>,
+    },
+    While {
+        condition: NodeId,
+        body: NodeId,
+    },
+    For {
+        init: NodeId,
+        end: NodeId,
+        step: Option<NodeId>,
+        body: NodeId,
+    },
+    Break,
+    Continue,
+    Return(Vec<NodeId>),
+    ErrorStmt,
+    Block(Vec<NodeId>),
+    Param {
+        name: NodeId,
+        ty: NodeId,
+    },
+    Ident(&'src str),
+    PrimitiveType(PrimitiveType),
+    NamedType(&'src str),
+    ArrayType {
+        element: NodeId,
+        dimensions: Vec<Option<u64>>,
+    },
+    TupleType(Vec<NodeId>),
+    Binary {
+        op: BinaryOp,
+        left: NodeId,
+        right: NodeId,
+    },
+    Unary {
+        op: UnaryOp,
+        operand: NodeId,
+    },
+    Number(NumericLiteral),
+    Boolean(bool),
+    Char(char),
+    String(Cow<'src, str>),
+    Variable(&'src str),
+    Nullptr,
+    Type(PrimitiveType),
+    Index {
+        target: NodeId,
+        index: Option<NodeId>,
+    },
+    Array(Vec<NodeId>),
+    Call {
+        callee: NodeId,
+        args: Vec<NodeId>,
+    },
+    ErrorExpr,
+}
+
+impl NodeKind<'_> {
+    /// `None` for the nodes the C++ `NodeType` has no kind for.
+    pub fn node_type(&self) -> Option<NodeType> {
+        let node_type = match self {
+            NodeKind::Declaration { .. }
+            | NodeKind::Assignment { .. }
+            | NodeKind::Expression(_)
+            | NodeKind::Main(_)
+            | NodeKind::Function { .. }
+            | NodeKind::If { .. }
+            | NodeKind::While { .. }
+            | NodeKind::For { .. }
+            | NodeKind::Break
+            | NodeKind::Continue
+            | NodeKind::Return(_) => NodeType::Statement,
+            NodeKind::ErrorStmt | NodeKind::ErrorExpr => NodeType::Error,
+            NodeKind::Binary { .. } => NodeType::BinaryExpression,
+            NodeKind::Unary { .. } => NodeType::UnaryExpression,
+            NodeKind::Number(_) => NodeType::Number,
+            NodeKind::Boolean(_) => NodeType::Boolean,
+            NodeKind::Char(_) => NodeType::Char,
+            NodeKind::String(_) => NodeType::String,
+            NodeKind::Variable(_) => NodeType::Variable,
+            NodeKind::Nullptr => NodeType::Nullptr,
+            NodeKind::Type(_) => NodeType::Type,
+            NodeKind::Index { .. } => NodeType::Index,
+            NodeKind::Array(_) => NodeType::Array,
+            NodeKind::Call { .. } => NodeType::Call,
+            NodeKind::Block(_)
+            | NodeKind::Param { .. }
+            | NodeKind::Ident(_)
+            | NodeKind::PrimitiveType(_)
+            | NodeKind::NamedType(_)
+            | NodeKind::ArrayType { .. }
+            | NodeKind::TupleType(_) => return None,
+        };
+        Some(node_type)
+    }
+
+    /// Ids of the children in source order.
+    fn child_ids(&self) -> Vec<NodeId> {
+        match self {
+            NodeKind::Declaration {
+                names, ty, values, ..
+            } => names.iter().chain(ty).chain(values).copied().collect(),
+            NodeKind::Assignment {
+                targets, values, ..
+            } => targets.iter().chain(values).copied().collect(),
+            NodeKind::Function {
+                name,
+                params,
+                returns,
+                body,
+                ..
+            } => std::iter::once(name)
+                .chain(params)
+                .chain(returns)
+                .chain([body])
+                .copied()
+                .collect(),
+            NodeKind::If {
+                condition,
+                then_block,
+                else_branch,
+            } => [condition, then_block]
+                .into_iter()
+                .chain(else_branch)
+                .copied()
+                .collect(),
+            NodeKind::For {
+                init,
+                end,
+                step,
+                body,
+            } => [init, end]
+                .into_iter()
+                .chain(step)
+                .chain([body])
+                .copied()
+                .collect(),
+            NodeKind::Expression(child)
+            | NodeKind::Main(child)
+            | NodeKind::Unary { operand: child, .. }
+            | NodeKind::ArrayType { element: child, .. } => vec![*child],
+            NodeKind::While {
+                condition: first,
+                body: second,
+            }
+            | NodeKind::Param {
+                name: first,
+                ty: second,
+            }
+            | NodeKind::Binary {
+                left: first,
+                right: second,
+                ..
+            } => vec![*first, *second],
+            NodeKind::Index { target, index } => {
+                std::iter::once(target).chain(index).copied().collect()
+            }
+            NodeKind::Call { callee, args } => {
+                std::iter::once(callee).chain(args).copied().collect()
+            }
+            NodeKind::Return(children)
+            | NodeKind::Block(children)
+            | NodeKind::TupleType(children)
+            | NodeKind::Array(children) => children.clone(),
+            NodeKind::Break
+            | NodeKind::Continue
+            | NodeKind::ErrorStmt
+            | NodeKind::Ident(_)
+            | NodeKind::PrimitiveType(_)
+            | NodeKind::NamedType(_)
+            | NodeKind::Number(_)
+            | NodeKind::Boolean(_)
+            | NodeKind::Char(_)
+            | NodeKind::String(_)
+            | NodeKind::Variable(_)
+            | NodeKind::Nullptr
+            | NodeKind::Type(_)
+            | NodeKind::ErrorExpr => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstNode<'src> {
+    pub kind: NodeKind<'src>,
+    pub span: Span,
+    pub parent: Option<NodeId>,
+    /// The same ids as in `kind`, in source order.
+    pub children: Vec<NodeId>,
+}
+
+/// A parsed program stored as a flat list of nodes with parent links, for lookups that walk up
+/// the tree such as going from a cursor offset to the enclosing statement.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AstArena<'src> {
+    nodes: Vec<AstNode<'src>>,
+    roots: Vec<NodeId>,
+}
+
+impl<'src> AstArena<'src> {
+    pub fn new(statements: Vec<Stmt<'src>>) -> Self {
+        let mut arena = AstArena::default();
+        arena.roots = statements
+            .into_iter()
+            .map(|statement| arena.add_stmt(statement))
+            .collect();
+        arena
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Top-level statements, in source order.
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&AstNode<'src>> {
+        self.nodes.get(id.index())
+    }
+
+    pub fn kind(&self, id: NodeId) -> &NodeKind<'src> {
+        &self[id].kind
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self[id].parent
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self[id].children
+    }
+
+    pub fn span(&self, id: NodeId) -> Span {
+        self[id].span
+    }
+
+    /// Parents of `id` from the closest up to its top-level statement, `id` excluded.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), move |&parent| self.parent(parent))
+    }
+
+    /// Innermost node whose span contains the byte `offset`.
+    pub fn node_at_offset(&self, offset: usize) -> Option<NodeId> {
+        let mut found = None;
+        let mut candidates = self.roots.as_slice();
+        // Descend as long as one of the children contains the offset.
+        while let Some(&id) = candidates
+            .iter()
+            .find(|&&id| self[id].span.contains(offset))
+        {
+            found = Some(id);
+            candidates = self.children(id);
+        }
+        found
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &AstNode<'src>)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeId(index as u32), node))
+    }
+
+    /// Stores a node whose children are already in the arena and links them to it.
+    fn add(&mut self, kind: NodeKind<'src>, span: Span) -> NodeId {
+        let id = NodeId(self.nodes.len() as u32);
+        let children = kind.child_ids();
+        for &child in &children {
+            self.nodes[child.index()].parent = Some(id);
+        }
+        self.nodes.push(AstNode {
+            kind,
+            span,
+            parent: None,
+            children,
+        });
+        id
+    }
+
+    fn add_all<T>(&mut self, items: Vec<T>, add: impl Fn(&mut Self, T) -> NodeId) -> Vec<NodeId> {
+        items.into_iter().map(|item| add(self, item)).collect()
+    }
+
+    fn add_stmt(&mut self, stmt: Stmt<'src>) -> NodeId {
+        let kind = match stmt.kind {
+            StmtKind::Declaration {
+                doc,
+                is_const,
+                names,
+                ty,
+                values,
+            } => NodeKind::Declaration {
+                doc,
+                is_const,
+                names: self.add_all(names, Self::add_ident),
+                ty: ty.map(|ty| self.add_type_expr(ty)),
+                values: self.add_all(values, Self::add_expr),
+            },
+            StmtKind::Assignment {
+                targets,
+                op,
+                values,
+            } => NodeKind::Assignment {
+                targets: self.add_all(targets, Self::add_expr),
+                op,
+                values: self.add_all(values, Self::add_expr),
+            },
+            StmtKind::Expression(expr) => NodeKind::Expression(self.add_expr(expr)),
+            StmtKind::Main(body) => NodeKind::Main(self.add_block(body)),
+            StmtKind::Function {
+                doc,
+                name,
+                params,
+                returns,
+                body,
+            } => NodeKind::Function {
+                doc,
+                name: self.add_ident(name),
+                params: self.add_all(params, Self::add_param),
+                returns: returns.map(|returns| self.add_type_expr(returns)),
+                body: self.add_block(body),
+            },
+            StmtKind::If {
+                condition,
+                then_block,
+                else_branch,
+            } => NodeKind::If {
+                condition: self.add_expr(condition),
+                then_block: self.add_block(then_block),
+                else_branch: else_branch.map(|else_branch| match else_branch {
+                    ElseBranch::If(stmt) => self.add_stmt(*stmt),
+                    ElseBranch::Block(block) => self.add_block(block),
+                }),
+            },
+            StmtKind::While { condition, body } => NodeKind::While {
+                condition: self.add_expr(condition),
+                body: self.add_block(body),
+            },
+            StmtKind::For {
+                init,
+                end,
+                step,
+                body,
+            } => NodeKind::For {
+                init: self.add_stmt(*init),
+                end: self.add_expr(end),
+                step: step.map(|step| self.add_expr(step)),
+                body: self.add_block(body),
+            },
+            StmtKind::Break => NodeKind::Break,
+            StmtKind::Continue => NodeKind::Continue,
+            StmtKind::Return(values) => NodeKind::Return(self.add_all(values, Self::add_expr)),
+            StmtKind::Error => NodeKind::ErrorStmt,
+        };
+        self.add(kind, stmt.span)
+    }
+
+    fn add_expr(&mut self, expr: Expr<'src>) -> NodeId {
+        let kind = match expr.kind {
+            ExprKind::Binary { op, left, right } => NodeKind::Binary {
+                op,
+                left: self.add_expr(*left),
+                right: self.add_expr(*right),
+            },
+            ExprKind::Unary { op, operand } => NodeKind::Unary {
+                op,
+                operand: self.add_expr(*operand),
+            },
+            ExprKind::Number(number) => NodeKind::Number(number),
+            ExprKind::Boolean(value) => NodeKind::Boolean(value),
+            ExprKind::Char(value) => NodeKind::Char(value),
+            ExprKind::String(value) => NodeKind::String(value),
+            ExprKind::Variable(name) => NodeKind::Variable(name),
+            ExprKind::Nullptr => NodeKind::Nullptr,
+            ExprKind::Type(ty) => NodeKind::Type(ty),
+            ExprKind::Index { target, index } => NodeKind::Index {
+                target: self.add_expr(*target),
+                index: index.map(|index| self.add_expr(*index)),
+            },
+            ExprKind::Array(elements) => NodeKind::Array(self.add_all(elements, Self::add_expr)),
+            ExprKind::Call { callee, args } => NodeKind::Call {
+                callee: self.add_expr(*callee),
+                args: self.add_all(args, Self::add_expr),
+            },
+            ExprKind::Error => NodeKind::ErrorExpr,
+        };
+        self.add(kind, expr.span)
+    }
+
+    fn add_block(&mut self, block: Block<'src>) -> NodeId {
+        let statements = self.add_all(block.statements, Self::add_stmt);
+        self.add(NodeKind::Block(statements), block.span)
+    }
+
+    fn add_param(&mut self, param: Param<'src>) -> NodeId {
+        let kind = NodeKind::Param {
+            name: self.add_ident(param.name),
+            ty: self.add_type_expr(param.ty),
+        };
+        self.add(kind, param.span)
+    }
+
+    fn add_type_expr(&mut self, ty: TypeExpr<'src>) -> NodeId {
+        let kind = match ty.kind {
+            TypeExprKind::Primitive(primitive) => NodeKind::PrimitiveType(primitive),
+            TypeExprKind::Named(name) => NodeKind::NamedType(name),
+            TypeExprKind::Array {
+                element,
+                dimensions,
+            } => NodeKind::ArrayType {
+                element: self.add_type_expr(*element),
+                dimensions,
+            },
+            TypeExprKind::Tuple(types) => {
+                NodeKind::TupleType(self.add_all(types, Self::add_type_expr))
+            }
+        };
+        self.add(kind, ty.span)
+    }
+
+    fn add_ident(&mut self, ident: Ident<'src>) -> NodeId {
+        self.add(NodeKind::Ident(ident.name), ident.span)
+    }
+}
+
+impl<'src> Index<NodeId> for AstArena<'src> {
+    type Output = AstNode<'src>;
+
+    fn index(&self, id: NodeId) -> &Self::Output {
+        &self.nodes[id.index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_node_at_offset() {
+        let input = "fun f(a: i8[]): i8 {\n\treturn g(a + 1)\n}\nmain {\n\tvar b: i8 = f({1})\n}";
+        let (statements, errors) = Parser::new("test.vn", input).parse();
+        assert!(errors.is_empty());
+        let arena = AstArena::new(statements);
+        assert_eq!(arena.roots().len(), 2);
+        for (id, node) in arena.iter() {
+            for &child in &node.children {
+                assert!(child < id);
+                assert_eq!(arena.parent(child), Some(id));
+            }
+            match node.parent {
+                Some(parent) => assert!(arena.children(parent).contains(&id)),
+                None => assert!(arena.roots().contains(&id)),
+            }
+        }
+
+        let offset = input.find("a + 1").unwrap();
+        let id = arena.node_at_offset(offset).unwrap();
+        assert_eq!(arena.kind(id), &NodeKind::Variable("a"));
+        assert_eq!(arena.span(id), Span::new(offset, offset + 1));
+        let path: Vec<&str> = std::iter::once(id)
+            .chain(arena.ancestors(id))
+            .map(|id| arena.span(id).slice(input))
+            .collect();
+        assert_eq!(
+            path,
+            [
+                "a",
+                "a + 1",
+                "g(a + 1)",
+                "return g(a + 1)",
+                "{\n\treturn g(a + 1)\n}",
+                "fun f(a: i8[]): i8 {\n\treturn g(a + 1)\n}",
+            ]
+        );
+        let root = arena.ancestors(id).last().unwrap();
+        assert_eq!(arena.roots()[0], root);
+        let NodeKind::Function { name, body, .. } = arena.kind(root) else {
+            panic!("expected a function");
+        };
+        assert_eq!(arena.kind(*name), &NodeKind::Ident("f"));
+        assert_eq!(arena.ancestors(id).nth(3), Some(*body));
+
+        let id = arena
+            .node_at_offset(input.find("i8[]").unwrap() + 2)
+            .unwrap();
+        let NodeKind::ArrayType { element, .. } = arena.kind(id) else {
+            panic!("expected an array type");
+        };
+        assert_eq!(
+            arena.kind(*element),
+            &NodeKind::PrimitiveType(PrimitiveType::I8)
+        );
+        assert_eq!(arena.span(id).slice(input), "i8[]");
+        let id = arena.node_at_offset(input.find("a:").unwrap()).unwrap();
+        assert_eq!(arena.kind(id), &NodeKind::Ident("a"));
+        assert!(matches!(
+            arena.kind(arena.parent(id).unwrap()),
+            NodeKind::Param { .. }
+        ));
+        let id = arena
+            .node_at_offset(input.find("{1}").unwrap() + 1)
+            .unwrap();
+        assert_eq!(arena.kind(id).node_type(), Some(NodeType::Number));
+        assert_eq!(arena.node_at_offset(input.len()), None);
+    }
+}
@@ -0,0 +1,268 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use super::node_type::NodeType;
+use super::type_expr::PrimitiveType;
+use crate::token::{NumericLiteral, Span, TokenType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Assign,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+    XorAssign,
+    RemAssign,
+    OrAssign,
+    AndAssign,
+    Or,
+    And,
+    Eq,
+    NotEq,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Xor,
+    Rem,
+    BitOr,
+    BitAnd,
+    ShiftLeft,
+    ShiftRight,
+    /// Member access `a.b`, a binary operator like in the C++ parser.
+    Dot,
+}
+
+impl BinaryOp {
+    pub fn from_token_type(token_type: &TokenType) -> Option<Self> {
+        let op = match token_type {
+            TokenType::Equal => BinaryOp::Assign,
+            TokenType::PlusEqual => BinaryOp::AddAssign,
+            TokenType::MinusEqual => BinaryOp::SubAssign,
+            TokenType::StarEqual => BinaryOp::MulAssign,
+            TokenType::DivideEqual => BinaryOp::DivAssign,
+            TokenType::XorEqual => BinaryOp::XorAssign,
+            TokenType::PercentEqual => BinaryOp::RemAssign,
+            TokenType::OrEqual => BinaryOp::OrAssign,
+            TokenType::AndEqual => BinaryOp::AndAssign,
+            TokenType::OrOr => BinaryOp::Or,
+            TokenType::AndAnd => BinaryOp::And,
+            TokenType::EqualEqual => BinaryOp::Eq,
+            TokenType::NotEqual => BinaryOp::NotEq,
+            TokenType::Less => BinaryOp::Less,
+            TokenType::LessEqual => BinaryOp::LessEq,
+            TokenType::Greater => BinaryOp::Greater,
+            TokenType::GreaterEqual => BinaryOp::GreaterEq,
+            TokenType::Plus => BinaryOp::Add,
+            TokenType::Minus => BinaryOp::Sub,
+            TokenType::Star => BinaryOp::Mul,
+            TokenType::Divide => BinaryOp::Div,
+            TokenType::Xor => BinaryOp::Xor,
+            TokenType::Percent => BinaryOp::Rem,
+            TokenType::Or => BinaryOp::BitOr,
+            TokenType::And => BinaryOp::BitAnd,
+            TokenType::ShiftLeft => BinaryOp::ShiftLeft,
+            TokenType::ShiftRight => BinaryOp::ShiftRight,
+            TokenType::Dot => BinaryOp::Dot,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOp::Assign => "=",
+            BinaryOp::AddAssign => "+=",
+            BinaryOp::SubAssign => "-=",
+            BinaryOp::MulAssign => "*=",
+            BinaryOp::DivAssign => "/=",
+            BinaryOp::XorAssign => "^=",
+            BinaryOp::RemAssign => "%=",
+            BinaryOp::OrAssign => "|=",
+            BinaryOp::AndAssign => "&=",
+            BinaryOp::Or => "||",
+            BinaryOp::And => "&&",
+            BinaryOp::Eq => "==",
+            BinaryOp::NotEq => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessEq => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterEq => ">=",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Xor => "^",
+            BinaryOp::Rem => "%",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::ShiftLeft => "<<",
+            BinaryOp::ShiftRight => ">>",
+            BinaryOp::Dot => ".",
+        }
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOp {
+    Plus,
+    Minus,
+    Not,
+    Increment,
+    Decrement,
+}
+
+impl UnaryOp {
+    pub fn from_token_type(token_type: &TokenType) -> Option<Self> {
+        let op = match token_type {
+            TokenType::Plus => UnaryOp::Plus,
+            TokenType::Minus => UnaryOp::Minus,
+            TokenType::Not => UnaryOp::Not,
+            TokenType::PlusPlus => UnaryOp::Increment,
+            TokenType::MinusMinus => UnaryOp::Decrement,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnaryOp::Plus => "+",
+            UnaryOp::Minus => "-",
+            UnaryOp::Not => "!",
+            UnaryOp::Increment => "++",
+            UnaryOp::Decrement => "--",
+        }
+    }
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind<'src> {
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr<'src>>,
+        right: Box<Expr<'src>>,
+    },
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expr<'src>>,
+    },
+    Number(NumericLiteral),
+    Boolean(bool),
+    Char(char),
+    /// Value with its escape sequences decoded.
+    String(Cow<'src, str>),
+    Variable(&'src str),
+    Nullptr,
+    /// A primitive type used as a value, like in `i8[2]` or a conversion `i8(x)`.
+    Type(PrimitiveType),
+    /// `target[index]`, or `target[]` when `index` is `None`.
+    Index {
+        target: Box<Expr<'src>>,
+        index: Option<Box<Expr<'src>>>,
+    },
+    /// `{a, b}` array literal.
+    Array(Vec<Expr<'src>>),
+    Call {
+        callee: Box<Expr<'src>>,
+        args: Vec<Expr<'src>>,
+    },
+    /// An element of a call or array literal that failed to parse.
+    Error,
+}
+
+/// Expression node; `span` covers all of its source text except enclosing parentheses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr<'src> {
+    pub kind: ExprKind<'src>,
+    pub span: Span,
+}
+
+impl<'src> Expr<'src> {
+    pub fn new(kind: ExprKind<'src>, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn node_type(&self) -> NodeType {
+        match self.kind {
+            ExprKind::Binary { .. } => NodeType::BinaryExpression,
+            ExprKind::Unary { .. } => NodeType::UnaryExpression,
+            ExprKind::Number(_) => NodeType::Number,
+            ExprKind::Boolean(_) => NodeType::Boolean,
+            ExprKind::Char(_) => NodeType::Char,
+            ExprKind::String(_) => NodeType::String,
+            ExprKind::Variable(_) => NodeType::Variable,
+            ExprKind::Nullptr => NodeType::Nullptr,
+            ExprKind::Type(_) => NodeType::Type,
+            ExprKind::Index { .. } => NodeType::Index,
+            ExprKind::Array(_) => NodeType::Array,
+            ExprKind::Call { .. } => NodeType::Call,
+            ExprKind::Error => NodeType::Error,
+        }
+    }
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, items: &[Expr<'_>]) -> fmt::Result {
+    for (position, item) in items.iter().enumerate() {
+        if position > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Prints the expression as source, with every binary and unary expression parenthesized so
+/// that the tree shape is visible: `a + b * c` prints as `(a + (b * c))`.
+impl fmt::Display for Expr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ExprKind::Binary {
+                op: BinaryOp::Dot,
+                left,
+                right,
+            } => write!(f, "({}.{})", left, right),
+            ExprKind::Binary { op, left, right } => write!(f, "({} {} {})", left, op, right),
+            ExprKind::Unary { op, operand } => write!(f, "({}{})", op, operand),
+            ExprKind::Number(numeric) => write!(f, "{}", numeric),
+            ExprKind::Boolean(value) => write!(f, "{}", value),
+            ExprKind::Char(value) => write!(f, "'{}'", value.escape_default()),
+            ExprKind::String(value) => write!(f, "\"{}\"", value.escape_default()),
+            ExprKind::Variable(name) => write!(f, "{}", name),
+            ExprKind::Nullptr => write!(f, "nullptr"),
+            ExprKind::Type(primitive) => write!(f, "{}", primitive),
+            ExprKind::Index { target, index } => match index {
+                Some(index) => write!(f, "{}[{}]", target, index),
+                None => write!(f, "{}[]", target),
+            },
+            ExprKind::Array(elements) => {
+                write!(f, "{{")?;
+                write_list(f, elements)?;
+                write!(f, "}}")
+            }
+            ExprKind::Call { callee, args } => {
+                write!(f, "{}(", callee)?;
+                write_list(f, args)?;
+                write!(f, ")")
+            }
+            ExprKind::Error => write!(f, "<error>"),
+        }
+    }
+}
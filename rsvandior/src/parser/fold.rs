@@ -0,0 +1,325 @@
+use super::ast::{Expr, ExprKind};
+use super::stmt::{Block, ElseBranch, Ident, Param, Stmt, StmtKind};
+use super::type_expr::{TypeExpr, TypeExprKind};
+
+/// Rewrites the AST by value. Every hook defaults to the matching `fold_*` function, which folds
+/// the children in source order and rebuilds the node with its original span; override a hook to
+/// replace a node, calling the `fold_*` function first to rewrite bottom-up.
+pub trait Fold<'src> {
+    fn fold_stmt(&mut self, stmt: Stmt<'src>) -> Stmt<'src> {
+        fold_stmt(self, stmt)
+    }
+
+    fn fold_expr(&mut self, expr: Expr<'src>) -> Expr<'src> {
+        fold_expr(self, expr)
+    }
+
+    fn fold_block(&mut self, block: Block<'src>) -> Block<'src> {
+        fold_block(self, block)
+    }
+
+    fn fold_else_branch(&mut self, else_branch: ElseBranch<'src>) -> ElseBranch<'src> {
+        fold_else_branch(self, else_branch)
+    }
+
+    fn fold_param(&mut self, param: Param<'src>) -> Param<'src> {
+        fold_param(self, param)
+    }
+
+    fn fold_type_expr(&mut self, ty: TypeExpr<'src>) -> TypeExpr<'src> {
+        fold_type_expr(self, ty)
+    }
+
+    fn fold_ident(&mut self, ident: Ident<'src>) -> Ident<'src> {
+        ident
+    }
+}
+
+fn fold_exprs<'src, F: Fold<'src> + ?Sized>(
+    folder: &mut F,
+    exprs: Vec<Expr<'src>>,
+) -> Vec<Expr<'src>> {
+    exprs
+        .into_iter()
+        .map(|expr| folder.fold_expr(expr))
+        .collect()
+}
+
+fn fold_boxed<'src, F: Fold<'src> + ?Sized>(
+    folder: &mut F,
+    mut expr: Box<Expr<'src>>,
+) -> Box<Expr<'src>> {
+    // Folds in place to reuse the box's allocation.
+    *expr = folder.fold_expr(*expr);
+    expr
+}
+
+pub fn fold_stmts<'src, F: Fold<'src> + ?Sized>(
+    folder: &mut F,
+    statements: Vec<Stmt<'src>>,
+) -> Vec<Stmt<'src>> {
+    statements
+        .into_iter()
+        .map(|statement| folder.fold_stmt(statement))
+        .collect()
+}
+
+pub fn fold_stmt<'src, F: Fold<'src> + ?Sized>(folder: &mut F, stmt: Stmt<'src>) -> Stmt<'src> {
+    let kind = match stmt.kind {
+        StmtKind::Declaration {
+            doc,
+            is_const,
+            names,
+            ty,
+            values,
+        } => StmtKind::Declaration {
+            doc,
+            is_const,
+            names: names
+                .into_iter()
+                .map(|name| folder.fold_ident(name))
+                .collect(),
+            ty: ty.map(|ty| folder.fold_type_expr(ty)),
+            values: fold_exprs(folder, values),
+        },
+        StmtKind::Assignment {
+            targets,
+            op,
+            values,
+        } => StmtKind::Assignment {
+            targets: fold_exprs(folder, targets),
+            op,
+            values: fold_exprs(folder, values),
+        },
+        StmtKind::Expression(expr) => StmtKind::Expression(folder.fold_expr(expr)),
+        StmtKind::Main(body) => StmtKind::Main(folder.fold_block(body)),
+        StmtKind::Function {
+            doc,
+            name,
+            params,
+            returns,
+            body,
+        } => StmtKind::Function {
+            doc,
+            name: folder.fold_ident(name),
+            params: params
+                .into_iter()
+                .map(|param| folder.fold_param(param))
+                .collect(),
+            returns: returns.map(|returns| folder.fold_type_expr(returns)),
+            body: folder.fold_block(body),
+        },
+        StmtKind::If {
+            condition,
+            then_block,
+            else_branch,
+        } => StmtKind::If {
+            condition: folder.fold_expr(condition),
+            then_block: folder.fold_block(then_block),
+            else_branch: else_branch.map(|else_branch| folder.fold_else_branch(else_branch)),
+        },
+        StmtKind::While { condition, body } => StmtKind::While {
+            condition: folder.fold_expr(condition),
+            body: folder.fold_block(body),
+        },
+        StmtKind::For {
+            init,
+            end,
+            step,
+            body,
+        } => StmtKind::For {
+            init: Box::new(folder.fold_stmt(*init)),
+            end: folder.fold_expr(end),
+            step: step.map(|step| folder.fold_expr(step)),
+            body: folder.fold_block(body),
+        },
+        StmtKind::Return(values) => StmtKind::Return(fold_exprs(folder, values)),
+        kind @ (StmtKind::Break | StmtKind::Continue | StmtKind::Error) => kind,
+    };
+    Stmt::new(kind, stmt.span)
+}
+
+pub fn fold_expr<'src, F: Fold<'src> + ?Sized>(folder: &mut F, expr: Expr<'src>) -> Expr<'src> {
+    let kind = match expr.kind {
+        ExprKind::Binary { op, left, right } => ExprKind::Binary {
+            op,
+            left: fold_boxed(folder, left),
+            right: fold_boxed(folder, right),
+        },
+        ExprKind::Unary { op, operand } => ExprKind::Unary {
+            op,
+            operand: fold_boxed(folder, operand),
+        },
+        ExprKind::Index { target, index } => ExprKind::Index {
+            target: fold_boxed(folder, target),
+            index: index.map(|index| fold_boxed(folder, index)),
+        },
+        ExprKind::Array(elements) => ExprKind::Array(fold_exprs(folder, elements)),
+        ExprKind::Call { callee, args } => ExprKind::Call {
+            callee: fold_boxed(folder, callee),
+            args: fold_exprs(folder, args),
+        },
+        kind @ (ExprKind::Number(_)
+        | ExprKind::Boolean(_)
+        | ExprKind::Char(_)
+        | ExprKind::String(_)
+        | ExprKind::Variable(_)
+        | ExprKind::Nullptr
+        | ExprKind::Type(_)
+        | ExprKind::Error) => kind,
+    };
+    Expr::new(kind, expr.span)
+}
+
+pub fn fold_block<'src, F: Fold<'src> + ?Sized>(folder: &mut F, block: Block<'src>) -> Block<'src> {
+    Block {
+        statements: fold_stmts(folder, block.statements),
+        span: block.span,
+    }
+}
+
+pub fn fold_else_branch<'src, F: Fold<'src> + ?Sized>(
+    folder: &mut F,
+    else_branch: ElseBranch<'src>,
+) -> ElseBranch<'src> {
+    match else_branch {
+        ElseBranch::If(stmt) => ElseBranch::If(Box::new(folder.fold_stmt(*stmt))),
+        ElseBranch::Block(block) => ElseBranch::Block(folder.fold_block(block)),
+    }
+}
+
+pub fn fold_param<'src, F: Fold<'src> + ?Sized>(folder: &mut F, param: Param<'src>) -> Param<'src> {
+    Param {
+        name: folder.fold_ident(param.name),
+        ty: folder.fold_type_expr(param.ty),
+        span: param.span,
+    }
+}
+
+pub fn fold_type_expr<'src, F: Fold<'src> + ?Sized>(
+    folder: &mut F,
+    ty: TypeExpr<'src>,
+) -> TypeExpr<'src> {
+    let kind = match ty.kind {
+        TypeExprKind::Array {
+            element,
+            dimensions,
+        } => TypeExprKind::Array {
+            element: Box::new(folder.fold_type_expr(*element)),
+            dimensions,
+        },
+        TypeExprKind::Tuple(types) => TypeExprKind::Tuple(
+            types
+                .into_iter()
+                .map(|ty| folder.fold_type_expr(ty))
+                .collect(),
+        ),
+        kind @ (TypeExprKind::Primitive(_) | TypeExprKind::Named(_)) => kind,
+    };
+    TypeExpr::new(kind, ty.span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{BinaryOp, Parser};
+    use crate::token::{NumericLiteral, NumericValue};
+
+    /// Folds additions and multiplications of integer literals.
+    struct ConstantFolder;
+
+    impl<'src> Fold<'src> for ConstantFolder {
+        fn fold_expr(&mut self, expr: Expr<'src>) -> Expr<'src> {
+            let expr = fold_expr(self, expr);
+            let ExprKind::Binary { op, left, right } = &expr.kind else {
+                return expr;
+            };
+            let (ExprKind::Number(a), ExprKind::Number(b)) = (&left.kind, &right.kind) else {
+                return expr;
+            };
+            let (NumericValue::Integer(x), NumericValue::Integer(y)) = (a.value, b.value) else {
+                return expr;
+            };
+            let value = match op {
+                BinaryOp::Add => x + y,
+                BinaryOp::Mul => x * y,
+                _ => return expr,
+            };
+            let number = NumericLiteral {
+                value: NumericValue::Integer(value),
+                ..*a
+            };
+            Expr::new(ExprKind::Number(number), expr.span)
+        }
+    }
+
+    #[test]
+    fn test_fold() {
+        let input =
+            "main {\n\tvar a: i8[] = {1 + 2 * 3, b + 1 * 2}\n\tif(c) {} else if(f(2 + 2)) {}\n}";
+        let (statements, errors) = Parser::new("test.vn", input).parse();
+        assert!(errors.is_empty());
+        let statements = fold_stmts(&mut ConstantFolder, statements);
+        let StmtKind::Main(body) = &statements[0].kind else {
+            panic!("expected main");
+        };
+        let StmtKind::Declaration { values, .. } = &body.statements[0].kind else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(values[0].to_string(), "{7, (b + 2)}");
+        assert_eq!(values[0].span.slice(input), "{1 + 2 * 3, b + 1 * 2}");
+        let StmtKind::If {
+            else_branch: Some(ElseBranch::If(else_if)),
+            ..
+        } = &body.statements[1].kind
+        else {
+            panic!("expected else if");
+        };
+        let StmtKind::If { condition, .. } = &else_if.kind else {
+            panic!("expected if");
+        };
+        assert_eq!(condition.to_string(), "f(4)");
+    }
+
+    /// Gives every array type without a size the size `n`.
+    struct SizeArrays(u64);
+
+    impl<'src> Fold<'src> for SizeArrays {
+        fn fold_type_expr(&mut self, ty: TypeExpr<'src>) -> TypeExpr<'src> {
+            let mut ty = fold_type_expr(self, ty);
+            if let TypeExprKind::Array { dimensions, .. } = &mut ty.kind {
+                for dimension in dimensions
+                    .iter_mut()
+                    .filter(|dimension| dimension.is_none())
+                {
+                    *dimension = Some(self.0);
+                }
+            }
+            ty
+        }
+    }
+
+    #[test]
+    fn test_fold_type_hook() {
+        let input = "fun f(a: i8[][2]): bool[] {\n\tvar b: Derived[] = a\n}";
+        let (statements, errors) = Parser::new("test.vn", input).parse();
+        assert!(errors.is_empty());
+        let statements = fold_stmts(&mut SizeArrays(3), statements);
+        let StmtKind::Function {
+            params,
+            returns,
+            body,
+            ..
+        } = &statements[0].kind
+        else {
+            panic!("expected a function");
+        };
+        assert_eq!(params[0].ty.to_string(), "i8[3][2]");
+        assert_eq!(params[0].ty.span.slice(input), "i8[][2]");
+        assert_eq!(returns.as_ref().unwrap().to_string(), "bool[3]");
+        let StmtKind::Declaration { ty, .. } = &body.statements[0].kind else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(ty.as_ref().unwrap().to_string(), "Derived[3]");
+    }
+}
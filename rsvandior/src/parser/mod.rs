@@ -0,0 +1,22 @@
+pub mod arena;
+pub mod ast;
+pub mod fold;
+pub mod node_type;
+pub mod parse_error;
+#[allow(clippy::module_inception)]
+pub mod parser;
+pub mod stmt;
+pub mod type_expr;
+pub mod visit;
+pub mod visit_mut;
+
+pub use arena::*;
+pub use ast::*;
+pub use fold::*;
+pub use node_type::*;
+pub use parse_error::*;
+pub use parser::*;
+pub use stmt::*;
+pub use type_expr::*;
+pub use visit::*;
+pub use visit_mut::*;
@@ -0,0 +1,50 @@
+use std::fmt;
+
+/// Kind of an AST node, matching the C++ `NodeType` plus `Call`, which the C++ parser stores
+/// inside its variable nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeType {
+    BinaryExpression,
+    UnaryExpression,
+    Number,
+    Boolean,
+    Char,
+    String,
+    Variable,
+    Nullptr,
+    Type,
+    Index,
+    Array,
+    Call,
+    Statement,
+    /// Placeholder the parser leaves where it recovered from a syntax error.
+    Error,
+}
+
+impl NodeType {
+    /// Same names as the fmt formatter of the C++ `NodeType`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeType::BinaryExpression => "BINARY_EXPRESION",
+            NodeType::UnaryExpression => "UNARY_EXPRESION",
+            NodeType::Number => "NUMBER",
+            NodeType::Boolean => "BOOLEAN",
+            NodeType::Char => "CHAR",
+            NodeType::String => "STRING",
+            NodeType::Variable => "VARIABLE",
+            NodeType::Nullptr => "NULLPTR",
+            NodeType::Type => "TYPE",
+            NodeType::Index => "INDEX",
+            NodeType::Array => "ARRAY",
+            NodeType::Call => "CALL",
+            NodeType::Statement => "STATEMENT",
+            NodeType::Error => "ERROR",
+        }
+    }
+}
+
+impl fmt::Display for NodeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::diagnostic::Diagnostic;
+use crate::token::{CodeSourceLocation, Span, Token};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    ExpectedExpression,
+    /// A `(`, `[` or `{` whose closing delimiter, stored here, is missing.
+    UnclosedDelimiter(char),
+    /// A token left over after a complete expression or statement.
+    UnexpectedToken,
+    /// One of `expected` was required here; `context` says where, as in "expected `:` or `=`
+    /// after variable name".
+    Expected {
+        expected: &'static [&'static str],
+        context: Option<&'static str>,
+    },
+}
+
+impl ParseErrorKind {
+    pub fn message(&self) -> String {
+        match self {
+            ParseErrorKind::ExpectedExpression => "expected expression".to_string(),
+            ParseErrorKind::UnclosedDelimiter(close) => format!("expected `{}`", close),
+            ParseErrorKind::UnexpectedToken => "unexpected token".to_string(),
+            ParseErrorKind::Expected { expected, context } => {
+                let mut message = format!("expected {}", join_alternatives(expected));
+                if let Some(context) = context {
+                    message.push(' ');
+                    message.push_str(context);
+                }
+                message
+            }
+        }
+    }
+
+    /// Stable code shown by rendered diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseErrorKind::ExpectedExpression => "P0001",
+            ParseErrorKind::UnclosedDelimiter(_) => "P0002",
+            ParseErrorKind::UnexpectedToken => "P0003",
+            ParseErrorKind::Expected { .. } => "P0004",
+        }
+    }
+}
+
+/// `a`, `a or b`, `a, b or c`.
+fn join_alternatives(items: &[&str]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.to_string(),
+        [rest @ .., last] => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParseError<'src> {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+    /// Text of the token the error was found at, empty at the end of the file.
+    pub lexeme: &'src str,
+    pub source_location: CodeSourceLocation,
+}
+
+impl<'src> ParseError<'src> {
+    pub fn new(
+        kind: ParseErrorKind,
+        span: Span,
+        lexeme: &'src str,
+        source_location: CodeSourceLocation,
+    ) -> Self {
+        Self {
+            kind,
+            span,
+            lexeme,
+            source_location,
+        }
+    }
+
+    pub fn at_token(kind: ParseErrorKind, token: &Token<'src>) -> Self {
+        Self::new(kind, token.span, token.value, token.source_location)
+    }
+}
+
+impl ParseError<'_> {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let label = if self.lexeme.is_empty() {
+            "found end of file".to_string()
+        } else {
+            format!("found '{}'", self.lexeme.escape_debug())
+        };
+        Diagnostic::error(self.source_location.file_id, self.kind.message())
+            .with_code(self.kind.code())
+            .with_primary_label(self.span, label)
+    }
+}
+
+impl fmt::Display for ParseError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' at {}",
+            self.kind,
+            self.lexeme.escape_debug(),
+            self.source_location
+        )
+    }
+}
+
+impl std::error::Error for ParseError<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::FileId;
+
+    #[test]
+    fn test_to_diagnostic() {
+        let error = ParseError::new(
+            ParseErrorKind::UnclosedDelimiter(')'),
+            Span::new(6, 6),
+            "",
+            CodeSourceLocation::new(FileId::intern("main.vn"), 1, 6),
+        );
+        let diagnostic = error.to_diagnostic();
+        assert_eq!(diagnostic.code.as_deref(), Some("P0002"));
+        assert_eq!(diagnostic.primary_span(), Some(Span::new(6, 6)));
+        assert_eq!(diagnostic.labels[0].message, "found end of file");
+        assert_eq!(format!("{}", diagnostic), "error[P0002]: expected `)`");
+    }
+
+    #[test]
+    fn test_expected_message() {
+        let kind = ParseErrorKind::Expected {
+            expected: &["`:`", "`=`"],
+            context: Some("after variable name"),
+        };
+        assert_eq!(kind.message(), "expected `:` or `=` after variable name");
+        let kind = ParseErrorKind::Expected {
+            expected: &["`,`", "`)`", "expression"],
+            context: None,
+        };
+        assert_eq!(kind.message(), "expected `,`, `)` or expression");
+    }
+}
@@ -0,0 +1,1292 @@
+use std::borrow::Cow;
+
+use super::ast::{BinaryOp, Expr, ExprKind, UnaryOp};
+use super::parse_error::{ParseError, ParseErrorKind};
+use super::stmt::{Block, ElseBranch, Ident, Param, Stmt, StmtKind};
+use super::type_expr::{PrimitiveType, TypeExpr, TypeExprKind};
+use crate::lexer::{LexError, Tokenizer};
+use crate::token::{
+    doc_comment_body, Associativity, CodeSourceLocation, FileId, NumericLiteral, Span, Token,
+    TokenType,
+};
+
+/// Recursive descent parser over the token stream of one file, the Rust side of `vnd::Parser`.
+///
+/// Comments are dropped up front and the stream always ends with `Eoft`. Doc comments are set
+/// aside first, so that a `fun`, `var` or `const` declaration can pick up the ones before it.
+pub struct Parser<'src> {
+    tokens: Vec<Token<'src>>,
+    /// Text of each run of doc comments, keyed by the index of the token that follows the run.
+    doc_comments: Vec<(usize, Cow<'src, str>)>,
+    lex_errors: Vec<LexError<'src>>,
+    errors: Vec<ParseError<'src>>,
+    position: usize,
+}
+
+impl<'src> Parser<'src> {
+    pub fn new(file_name: &'src str, input: &'src str) -> Self {
+        let (tokens, lex_errors) = Tokenizer::new(file_name, input).tokenize();
+        Self {
+            lex_errors,
+            ..Self::new_with_tokens(tokens)
+        }
+    }
+
+    pub fn new_with_tokens(tokens: Vec<Token<'src>>) -> Self {
+        let mut code_tokens: Vec<Token<'src>> = Vec::with_capacity(tokens.len());
+        let mut doc_comments = Vec::new();
+        let mut doc_lines: Vec<Cow<'src, str>> = Vec::new();
+        for token in tokens {
+            match token.token_type {
+                TokenType::DocComment => doc_lines.push(
+                    token
+                        .cooked
+                        .unwrap_or_else(|| doc_comment_body(token.value)),
+                ),
+                TokenType::Comment => {}
+                _ => {
+                    if !doc_lines.is_empty() {
+                        let doc = if doc_lines.len() == 1 {
+                            doc_lines.remove(0)
+                        } else {
+                            Cow::Owned(doc_lines.join("\n"))
+                        };
+                        doc_lines.clear();
+                        doc_comments.push((code_tokens.len(), doc));
+                    }
+                    code_tokens.push(token);
+                }
+            }
+        }
+        let mut tokens = code_tokens;
+        if tokens
+            .last()
+            .is_none_or(|token| token.token_type != TokenType::Eoft)
+        {
+            let (span, source_location) = tokens.last().map_or_else(
+                || {
+                    (
+                        Span::default(),
+                        CodeSourceLocation::new(FileId::intern(""), 1, 1),
+                    )
+                },
+                |last| {
+                    (
+                        Span::new(last.span.end, last.span.end),
+                        last.source_location,
+                    )
+                },
+            );
+            tokens.push(Token::new_with_span(
+                TokenType::Eoft,
+                "",
+                span,
+                source_location,
+            ));
+        }
+        Self {
+            tokens,
+            doc_comments,
+            lex_errors: Vec::new(),
+            errors: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Errors of the tokenizer, whose offending text is missing from the token stream.
+    pub fn lex_errors(&self) -> &[LexError<'src>] {
+        &self.lex_errors
+    }
+
+    /// Parses a whole file into its top-level statements, together with every syntax error found.
+    ///
+    /// Statements that fail to parse become `StmtKind::Error` and parsing resumes at the next
+    /// line, so one run reports all the errors of the file.
+    pub fn parse(&mut self) -> (Vec<Stmt<'src>>, Vec<ParseError<'src>>) {
+        let mut statements = Vec::new();
+        while !self.is_current(&TokenType::Eoft) {
+            statements.push(self.parse_statement());
+        }
+        (statements, std::mem::take(&mut self.errors))
+    }
+
+    /// Parses one statement, which must end its line, recovering from its errors.
+    fn parse_statement(&mut self) -> Stmt<'src> {
+        let start_position = self.position;
+        let start = self.current().span;
+        match self.parse_statement_kind() {
+            Ok(kind) => {
+                let statement = Stmt::new(kind, self.span_from(start));
+                if !self.is_statement_end() {
+                    self.errors.push(ParseError::at_token(
+                        ParseErrorKind::UnexpectedToken,
+                        self.current(),
+                    ));
+                    self.synchronize(start_position);
+                }
+                statement
+            }
+            Err(error) => {
+                self.errors.push(error);
+                self.synchronize(start_position);
+                Stmt::new(StmtKind::Error, self.span_from(start))
+            }
+        }
+    }
+
+    fn parse_statement_kind(&mut self) -> Result<StmtKind<'src>, ParseError<'src>> {
+        let kind = match self.current().token_type {
+            TokenType::KVar | TokenType::KConst => self.parse_declaration()?,
+            TokenType::KMain => {
+                self.advance();
+                StmtKind::Main(self.parse_block("after `main`")?)
+            }
+            TokenType::KFun => self.parse_function()?,
+            TokenType::KIf => self.parse_if()?,
+            TokenType::KWhile => {
+                self.advance();
+                let condition = self.parse_expression()?;
+                let body = self.parse_block("after the loop condition")?;
+                StmtKind::While { condition, body }
+            }
+            TokenType::KFor => self.parse_for()?,
+            TokenType::KBreak => {
+                self.advance();
+                StmtKind::Break
+            }
+            TokenType::KContinue => {
+                self.advance();
+                StmtKind::Continue
+            }
+            TokenType::KReturn => {
+                self.advance();
+                if self.is_statement_end() {
+                    StmtKind::Return(Vec::new())
+                } else {
+                    StmtKind::Return(self.parse_expression_list()?)
+                }
+            }
+            _ => self.parse_assignment_or_expression()?,
+        };
+        Ok(kind)
+    }
+
+    /// Skips what is left of a statement that failed to parse: the rest of its line, including
+    /// any `{ }` block opened on it, stopping before a `}` that closes an enclosing block. At
+    /// least one token is always skipped so that parsing makes progress.
+    fn synchronize(&mut self, start_position: usize) {
+        let mut depth: usize = 0;
+        let mut must_advance = self.position == start_position;
+        loop {
+            let current = self.current();
+            let at_boundary = match current.token_type {
+                TokenType::Eoft => return,
+                TokenType::CloseCurParenthesis => depth == 0,
+                _ => {
+                    depth == 0
+                        && current.source_location.line > self.previous().source_location.end_line
+                }
+            };
+            if at_boundary && !must_advance {
+                return;
+            }
+            must_advance = false;
+            match current.token_type {
+                TokenType::OpenCurParenthesis => depth += 1,
+                TokenType::CloseCurParenthesis => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    /// Parses one expression starting at the current token.
+    pub fn parse_expression(&mut self) -> Result<Expr<'src>, ParseError<'src>> {
+        self.parse_binary(0)
+    }
+
+    fn current(&self) -> &Token<'src> {
+        &self.tokens[self.position]
+    }
+
+    /// Doc comments right before the current token, one line each.
+    fn doc_comment(&self) -> Option<Cow<'src, str>> {
+        let index = self
+            .doc_comments
+            .binary_search_by_key(&self.position, |(index, _)| *index)
+            .ok()?;
+        Some(self.doc_comments[index].1.clone())
+    }
+
+    fn is_current(&self, token_type: &TokenType) -> bool {
+        self.current().is_type(token_type)
+    }
+
+    /// Returns the current token and moves past it; `Eoft` is never consumed.
+    fn advance(&mut self) -> Token<'src> {
+        let token = self.current().clone();
+        if self.position + 1 < self.tokens.len() {
+            self.position += 1;
+        }
+        token
+    }
+
+    /// The last consumed token, or the first one before anything is consumed.
+    fn previous(&self) -> &Token<'src> {
+        &self.tokens[self.position.saturating_sub(1)]
+    }
+
+    /// Span from `start` to the end of the last consumed token.
+    fn span_from(&self, start: Span) -> Span {
+        start.to(self.previous().span)
+    }
+
+    /// Statements are separated by newlines, so one ends before a token on a later line, a `}`
+    /// or the end of the file.
+    fn is_statement_end(&self) -> bool {
+        let current = self.current();
+        matches!(
+            current.token_type,
+            TokenType::Eoft | TokenType::CloseCurParenthesis
+        ) || current.source_location.line > self.previous().source_location.end_line
+    }
+
+    /// Error at the current token for a missing `expected`.
+    fn expected(
+        &self,
+        expected: &'static [&'static str],
+        context: &'static str,
+    ) -> ParseError<'src> {
+        let kind = ParseErrorKind::Expected {
+            expected,
+            context: Some(context).filter(|context| !context.is_empty()),
+        };
+        ParseError::at_token(kind, self.current())
+    }
+
+    fn expect(
+        &mut self,
+        token_type: TokenType,
+        expected: &'static [&'static str],
+        context: &'static str,
+    ) -> Result<Token<'src>, ParseError<'src>> {
+        if self.is_current(&token_type) {
+            Ok(self.advance())
+        } else {
+            Err(self.expected(expected, context))
+        }
+    }
+
+    fn expect_closing(
+        &mut self,
+        token_type: TokenType,
+        close: char,
+    ) -> Result<Span, ParseError<'src>> {
+        if self.is_current(&token_type) {
+            Ok(self.advance().span)
+        } else {
+            Err(ParseError::at_token(
+                ParseErrorKind::UnclosedDelimiter(close),
+                self.current(),
+            ))
+        }
+    }
+
+    fn parse_binary(&mut self, parent_precedence: u8) -> Result<Expr<'src>, ParseError<'src>> {
+        let start = self.current().span;
+        let mut left = self.parse_unary(parent_precedence)?;
+        loop {
+            let token_type = self.current().token_type.clone();
+            // `,` and `:` have a precedence but are separators, handled by the caller.
+            let (Some(op), Some(precedence)) = (
+                BinaryOp::from_token_type(&token_type),
+                token_type.binary_precedence(),
+            ) else {
+                break;
+            };
+            if precedence <= parent_precedence {
+                break;
+            }
+            self.advance();
+            let right_precedence = match token_type.associativity() {
+                Associativity::Left => precedence,
+                Associativity::Right => precedence - 1,
+            };
+            let right = self.parse_binary(right_precedence)?;
+            // From the first token, so that a parenthesized left operand keeps its `(`.
+            let span = self.span_from(start);
+            left = Expr::new(
+                ExprKind::Binary {
+                    op,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                span,
+            );
+        }
+        Ok(left)
+    }
+
+    /// Prefix operators, with the precedence from `TokenType::unary_precedence` like the C++
+    /// `parseUnary`.
+    fn parse_unary(&mut self, parent_precedence: u8) -> Result<Expr<'src>, ParseError<'src>> {
+        let token_type = &self.current().token_type;
+        let (Some(op), Some(precedence)) = (
+            UnaryOp::from_token_type(token_type),
+            token_type.unary_precedence(),
+        ) else {
+            return self.parse_primary();
+        };
+        if precedence < parent_precedence {
+            return self.parse_primary();
+        }
+        let start = self.advance().span;
+        let operand = self.parse_binary(precedence)?;
+        let span = self.span_from(start);
+        Ok(Expr::new(
+            ExprKind::Unary {
+                op,
+                operand: Box::new(operand),
+            },
+            span,
+        ))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr<'src>, ParseError<'src>> {
+        let token = self.current().clone();
+        let span = token.span;
+        if let Some(primitive) = PrimitiveType::from_token_type(&token.token_type) {
+            self.advance();
+            let node = Expr::new(ExprKind::Type(primitive), span);
+            return self.parse_postfix(node);
+        }
+        let kind = match &token.token_type {
+            TokenType::Integer | TokenType::Double => {
+                let numeric = token
+                    .numeric
+                    .or_else(|| NumericLiteral::parse(token.value).ok())
+                    .ok_or_else(|| {
+                        ParseError::at_token(ParseErrorKind::ExpectedExpression, &token)
+                    })?;
+                ExprKind::Number(numeric)
+            }
+            TokenType::Boolean => ExprKind::Boolean(token.value == "true"),
+            TokenType::Char => {
+                let value = token.cooked.as_deref().unwrap_or(token.value);
+                ExprKind::Char(value.chars().next().unwrap_or_default())
+            }
+            TokenType::String => {
+                ExprKind::String(token.cooked.clone().unwrap_or(Cow::Borrowed(token.value)))
+            }
+            TokenType::Identifier => {
+                self.advance();
+                let node = Expr::new(ExprKind::Variable(token.value), span);
+                return self.parse_postfix(node);
+            }
+            TokenType::KNullptr => ExprKind::Nullptr,
+            TokenType::OpenParenthesis => {
+                self.advance();
+                let expression = self.parse_expression()?;
+                self.expect_closing(TokenType::CloseParenthesis, ')')?;
+                return Ok(expression);
+            }
+            TokenType::OpenCurParenthesis => {
+                self.advance();
+                let elements = self.parse_list(
+                    TokenType::CloseCurParenthesis,
+                    &["`,`", "`}`"],
+                    "in array literal",
+                )?;
+                let end = self.expect_closing(TokenType::CloseCurParenthesis, '}')?;
+                return Ok(Expr::new(ExprKind::Array(elements), span.to(end)));
+            }
+            _ => {
+                return Err(ParseError::at_token(
+                    ParseErrorKind::ExpectedExpression,
+                    &token,
+                ))
+            }
+        };
+        self.advance();
+        Ok(Expr::new(kind, span))
+    }
+
+    /// Calls and indexing after a variable or a type: `f(a)[0]`, `i8[][2]`.
+    fn parse_postfix(&mut self, mut node: Expr<'src>) -> Result<Expr<'src>, ParseError<'src>> {
+        loop {
+            if self.is_current(&TokenType::OpenParenthesis) {
+                self.advance();
+                let args = self.parse_list(
+                    TokenType::CloseParenthesis,
+                    &["`,`", "`)`"],
+                    "in argument list",
+                )?;
+                let end = self.expect_closing(TokenType::CloseParenthesis, ')')?;
+                let span = node.span.to(end);
+                node = Expr::new(
+                    ExprKind::Call {
+                        callee: Box::new(node),
+                        args,
+                    },
+                    span,
+                );
+            } else if self.is_current(&TokenType::OpenSqParenthesis) {
+                node = self.parse_index(node)?;
+            } else {
+                return Ok(node);
+            }
+        }
+    }
+
+    /// `target[index]` or `target[]`, starting at the `[`.
+    fn parse_index(&mut self, target: Expr<'src>) -> Result<Expr<'src>, ParseError<'src>> {
+        self.advance();
+        let index = if self.is_current(&TokenType::CloseSqParenthesis) {
+            None
+        } else {
+            Some(Box::new(self.parse_expression()?))
+        };
+        let end = self.expect_closing(TokenType::CloseSqParenthesis, ']')?;
+        let span = target.span.to(end);
+        Ok(Expr::new(
+            ExprKind::Index {
+                target: Box::new(target),
+                index,
+            },
+            span,
+        ))
+    }
+
+    /// A primitive or user type with its array dimensions: `i8[][2]`, `Derived[2]`.
+    fn parse_type(&mut self) -> Result<TypeExpr<'src>, ParseError<'src>> {
+        let token = self.current().clone();
+        let kind = if let Some(primitive) = PrimitiveType::from_token_type(&token.token_type) {
+            TypeExprKind::Primitive(primitive)
+        } else if token.is_type(&TokenType::Identifier) {
+            TypeExprKind::Named(token.value)
+        } else {
+            return Err(self.expected(&["type"], ""));
+        };
+        self.advance();
+        let element = TypeExpr::new(kind, token.span);
+        let mut dimensions = Vec::new();
+        while self.is_current(&TokenType::OpenSqParenthesis) {
+            self.advance();
+            let size_token = self.current().clone();
+            // Only a plain integer, without the `i` or `f` suffix, is an array size.
+            let size = size_token
+                .numeric
+                .filter(|numeric| {
+                    size_token.is_type(&TokenType::Integer) && !numeric.imaginary && !numeric.is_f32
+                })
+                .and_then(|numeric| numeric.as_u64());
+            let size = match size {
+                Some(size) => {
+                    self.advance();
+                    Some(size)
+                }
+                None if size_token.is_type(&TokenType::CloseSqParenthesis) => None,
+                None => return Err(self.expected(&["array size", "`]`"], "")),
+            };
+            self.expect_closing(TokenType::CloseSqParenthesis, ']')?;
+            dimensions.push(size);
+        }
+        if dimensions.is_empty() {
+            return Ok(element);
+        }
+        let span = self.span_from(token.span);
+        Ok(TypeExpr::new(
+            TypeExprKind::Array {
+                element: Box::new(element),
+                dimensions,
+            },
+            span,
+        ))
+    }
+
+    /// Return types after `:`, a `Tuple` when there is more than one.
+    fn parse_return_types(&mut self) -> Result<TypeExpr<'src>, ParseError<'src>> {
+        let mut types = vec![self.parse_type()?];
+        while self.is_current(&TokenType::Comma) {
+            self.advance();
+            types.push(self.parse_type()?);
+        }
+        Ok(TypeExpr::from_returns(types).expect("at least one return type"))
+    }
+
+    /// A name, described by `expected` in the error when it is missing.
+    fn parse_ident(
+        &mut self,
+        expected: &'static [&'static str],
+    ) -> Result<Ident<'src>, ParseError<'src>> {
+        let token = self.expect(TokenType::Identifier, expected, "")?;
+        Ok(Ident::new(token.value, token.span))
+    }
+
+    /// One or more comma separated expressions.
+    fn parse_expression_list(&mut self) -> Result<Vec<Expr<'src>>, ParseError<'src>> {
+        let mut items = vec![self.parse_expression()?];
+        while self.is_current(&TokenType::Comma) {
+            self.advance();
+            items.push(self.parse_expression()?);
+        }
+        Ok(items)
+    }
+
+    /// `{ statements }`; `context` tells where the `{` was expected.
+    fn parse_block(&mut self, context: &'static str) -> Result<Block<'src>, ParseError<'src>> {
+        let start = self
+            .expect(TokenType::OpenCurParenthesis, &["`{`"], context)?
+            .span;
+        let mut statements = Vec::new();
+        while !matches!(
+            self.current().token_type,
+            TokenType::CloseCurParenthesis | TokenType::Eoft
+        ) {
+            statements.push(self.parse_statement());
+        }
+        let end = self.expect_closing(TokenType::CloseCurParenthesis, '}')?;
+        Ok(Block {
+            statements,
+            span: start.to(end),
+        })
+    }
+
+    fn parse_declaration(&mut self) -> Result<StmtKind<'src>, ParseError<'src>> {
+        let doc = self.doc_comment();
+        let is_const = self.advance().is_type(&TokenType::KConst);
+        let mut names = vec![self.parse_ident(&["variable name"])?];
+        while self.is_current(&TokenType::Comma) {
+            self.advance();
+            names.push(self.parse_ident(&["variable name"])?);
+        }
+        if !self.is_current(&TokenType::Colon) && !self.is_current(&TokenType::Equal) {
+            return Err(self.expected(&["`:`", "`=`"], "after variable name"));
+        }
+        let ty = if self.is_current(&TokenType::Colon) {
+            self.advance();
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        let values = if self.is_current(&TokenType::Equal) {
+            self.advance();
+            self.parse_expression_list()?
+        } else {
+            Vec::new()
+        };
+        Ok(StmtKind::Declaration {
+            doc,
+            is_const,
+            names,
+            ty,
+            values,
+        })
+    }
+
+    /// Operand of an assignment: everything that binds tighter than `=`.
+    fn parse_assignment_target(&mut self) -> Result<Expr<'src>, ParseError<'src>> {
+        self.parse_binary(TokenType::Equal.binary_precedence().unwrap_or_default())
+    }
+
+    fn parse_assignment_or_expression(&mut self) -> Result<StmtKind<'src>, ParseError<'src>> {
+        let mut targets = vec![self.parse_assignment_target()?];
+        while self.is_current(&TokenType::Comma) {
+            self.advance();
+            targets.push(self.parse_assignment_target()?);
+        }
+        let token_type = self.current().token_type.clone();
+        if let Some(op) =
+            BinaryOp::from_token_type(&token_type).filter(|_| token_type.is_assignment_operator())
+        {
+            self.advance();
+            let values = self.parse_expression_list()?;
+            return Ok(StmtKind::Assignment {
+                targets,
+                op,
+                values,
+            });
+        }
+        if targets.len() > 1 {
+            return Err(self.expected(&["`=`"], "after the assignment targets"));
+        }
+        Ok(StmtKind::Expression(targets.remove(0)))
+    }
+
+    fn parse_function(&mut self) -> Result<StmtKind<'src>, ParseError<'src>> {
+        let doc = self.doc_comment();
+        self.advance();
+        let name = self.parse_ident(&["function name"])?;
+        self.expect(TokenType::OpenParenthesis, &["`(`"], "after function name")?;
+        let mut params = Vec::new();
+        while !self.is_current(&TokenType::CloseParenthesis) {
+            let param_name = self.parse_ident(&["parameter name", "`)`"])?;
+            self.expect(TokenType::Colon, &["`:`"], "after parameter name")?;
+            let ty = self.parse_type()?;
+            let span = param_name.span.to(ty.span);
+            params.push(Param {
+                name: param_name,
+                ty,
+                span,
+            });
+            if self.is_current(&TokenType::Comma) {
+                self.advance();
+            } else if !self.is_current(&TokenType::CloseParenthesis) {
+                return Err(self.expected(&["`,`", "`)`"], "in parameter list"));
+            }
+        }
+        self.advance();
+        let returns = if self.is_current(&TokenType::Colon) {
+            self.advance();
+            Some(self.parse_return_types()?)
+        } else if self.is_current(&TokenType::OpenCurParenthesis) {
+            None
+        } else {
+            return Err(self.expected(&["`:`", "`{`"], "after parameter list"));
+        };
+        let body = self.parse_block("after the return types")?;
+        Ok(StmtKind::Function {
+            doc,
+            name,
+            params,
+            returns,
+            body,
+        })
+    }
+
+    fn parse_if(&mut self) -> Result<StmtKind<'src>, ParseError<'src>> {
+        self.advance();
+        let condition = self.parse_expression()?;
+        let then_block = self.parse_block("after the condition")?;
+        let else_branch = if self.is_current(&TokenType::KElse) {
+            self.advance();
+            if self.is_current(&TokenType::KIf) {
+                let start = self.current().span;
+                let kind = self.parse_if()?;
+                let span = self.span_from(start);
+                Some(ElseBranch::If(Box::new(Stmt::new(kind, span))))
+            } else if self.is_current(&TokenType::OpenCurParenthesis) {
+                Some(ElseBranch::Block(self.parse_block("after `else`")?))
+            } else {
+                return Err(self.expected(&["`if`", "`{`"], "after `else`"));
+            }
+        } else {
+            None
+        };
+        Ok(StmtKind::If {
+            condition,
+            then_block,
+            else_branch,
+        })
+    }
+
+    /// `for var i: T = start, end, step { }` or `for i = start, end, step { }`, where the step is
+    /// optional.
+    fn parse_for(&mut self) -> Result<StmtKind<'src>, ParseError<'src>> {
+        self.advance();
+        let start = self.current().span;
+        let init = if self.is_current(&TokenType::KVar) {
+            self.advance();
+            let name = self.parse_ident(&["loop variable name"])?;
+            self.expect(TokenType::Colon, &["`:`"], "after loop variable name")?;
+            let ty = self.parse_type()?;
+            self.expect(TokenType::Equal, &["`=`"], "after loop variable type")?;
+            StmtKind::Declaration {
+                doc: None,
+                is_const: false,
+                names: vec![name],
+                ty: Some(ty),
+                values: vec![self.parse_expression()?],
+            }
+        } else {
+            let target = self.parse_assignment_target()?;
+            self.expect(TokenType::Equal, &["`=`"], "after loop variable")?;
+            StmtKind::Assignment {
+                targets: vec![target],
+                op: BinaryOp::Assign,
+                values: vec![self.parse_expression()?],
+            }
+        };
+        let init = Box::new(Stmt::new(init, self.span_from(start)));
+        self.expect(TokenType::Comma, &["`,`"], "after the start value")?;
+        let end = self.parse_expression()?;
+        let step = if self.is_current(&TokenType::Comma) {
+            self.advance();
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+        let body = self.parse_block("after the loop range")?;
+        Ok(StmtKind::For {
+            init,
+            end,
+            step,
+            body,
+        })
+    }
+
+    /// Comma separated expressions up to, but not including, `close`. Elements that fail to
+    /// parse become `ExprKind::Error` when the list can resume at a following `,` or `close`.
+    fn parse_list(
+        &mut self,
+        close: TokenType,
+        expected: &'static [&'static str],
+        context: &'static str,
+    ) -> Result<Vec<Expr<'src>>, ParseError<'src>> {
+        let mut items = Vec::new();
+        if self.is_current(&close) {
+            return Ok(items);
+        }
+        loop {
+            let start = self.position;
+            let item = match self.parse_expression() {
+                Ok(item) => item,
+                Err(error) => {
+                    let span = self.recover_in_list(error, start, &close)?;
+                    Expr::new(ExprKind::Error, span)
+                }
+            };
+            items.push(item);
+            if !self.is_current(&TokenType::Comma) && !self.is_current(&close) {
+                let error = self.expected(expected, context);
+                self.recover_in_list(error, self.position, &close)?;
+            }
+            if !self.is_current(&TokenType::Comma) {
+                return Ok(items);
+            }
+            self.advance();
+        }
+    }
+
+    /// Looks for the next `,` or `close` of the list on the current line, skipping nested
+    /// brackets, including those opened by the element since `start`. When found the error is
+    /// recorded, the tokens from `start` are skipped and their span returned; otherwise the error
+    /// is handed back for statement level recovery.
+    fn recover_in_list(
+        &mut self,
+        error: ParseError<'src>,
+        start: usize,
+        close: &TokenType,
+    ) -> Result<Span, ParseError<'src>> {
+        let line = self.previous().source_location.end_line;
+        let mut depth = self.tokens[start..self.position]
+            .iter()
+            .fold(0, |depth: usize, token| match token.token_type {
+                TokenType::OpenParenthesis
+                | TokenType::OpenSqParenthesis
+                | TokenType::OpenCurParenthesis => depth + 1,
+                TokenType::CloseParenthesis
+                | TokenType::CloseSqParenthesis
+                | TokenType::CloseCurParenthesis => depth.saturating_sub(1),
+                _ => depth,
+            });
+        let mut position = self.position;
+        loop {
+            let token = &self.tokens[position];
+            if token.is_type(&TokenType::Eoft) || token.source_location.line > line {
+                return Err(error);
+            }
+            match token.token_type {
+                TokenType::OpenParenthesis
+                | TokenType::OpenSqParenthesis
+                | TokenType::OpenCurParenthesis => depth += 1,
+                TokenType::Comma if depth == 0 => break,
+                TokenType::CloseParenthesis
+                | TokenType::CloseSqParenthesis
+                | TokenType::CloseCurParenthesis
+                    if depth > 0 =>
+                {
+                    depth -= 1
+                }
+                _ if &token.token_type == close => break,
+                TokenType::CloseParenthesis
+                | TokenType::CloseSqParenthesis
+                | TokenType::CloseCurParenthesis => return Err(error),
+                _ => {}
+            }
+            position += 1;
+        }
+        let span = if position > start {
+            Span::new(
+                self.tokens[start].span.start,
+                self.tokens[position - 1].span.end,
+            )
+        } else {
+            Span::new(error.span.start, error.span.start)
+        };
+        self.position = position;
+        self.errors.push(error);
+        Ok(span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::NodeType;
+
+    fn parse_expression(input: &str) -> Result<Expr<'_>, ParseError<'_>> {
+        let mut parser = Parser::new("test.vn", input);
+        let expression = parser.parse_expression()?;
+        assert!(parser.lex_errors().is_empty());
+        if let Some(error) = parser.errors.first() {
+            return Err(error.clone());
+        }
+        if !parser.is_current(&TokenType::Eoft) {
+            return Err(ParseError::at_token(
+                ParseErrorKind::UnexpectedToken,
+                parser.current(),
+            ));
+        }
+        Ok(expression)
+    }
+
+    fn parse(input: &str) -> Expr<'_> {
+        parse_expression(input).unwrap()
+    }
+
+    fn parse_err(input: &str) -> ParseError<'_> {
+        parse_expression(input).unwrap_err()
+    }
+
+    fn parse_statements(input: &str) -> Vec<Stmt<'_>> {
+        let mut parser = Parser::new("test.vn", input);
+        let (statements, errors) = parser.parse();
+        assert!(parser.lex_errors().is_empty());
+        assert!(errors.is_empty(), "{:?}", errors);
+        statements
+    }
+
+    fn first_error(input: &str) -> ParseErrorKind {
+        let (_, errors) = Parser::new("test.vn", input).parse();
+        errors[0].kind.clone()
+    }
+
+    #[test]
+    fn test_operator_precedence() {
+        assert_eq!(parse("1 + 2 * 3").to_string(), "(1 + (2 * 3))");
+        assert_eq!(parse("a - b - c").to_string(), "((a - b) - c)");
+        assert_eq!(
+            parse("1 == 1 || !false && 67 < 77.85").to_string(),
+            "((1 == 1) || ((!false) && (67 < 77.85)))"
+        );
+        assert_eq!(
+            parse("10 / 2 ^ 2 ^ 3.4").to_string(),
+            "(10 / ((2 ^ 2) ^ 3.4))"
+        );
+        assert_eq!(parse("a = b += c").to_string(), "(a = (b += c))");
+        assert_eq!(parse("(1 + 2) * -3").to_string(), "((1 + 2) * (-3))");
+        assert_eq!(parse("- -a * b").to_string(), "((-(-a)) * b)");
+        assert_eq!(parse("!a.b").to_string(), "((!a).b)");
+        assert_eq!(
+            parse("a << 1 + b | c & d >> 2").to_string(),
+            "(((a << 1) + b) | ((c & d) >> 2))"
+        );
+        assert_eq!(parse("a |= b &= c").to_string(), "(a |= (b &= c))");
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(
+            parse("#ff").kind,
+            ExprKind::Number(NumericLiteral::parse("#ff").unwrap())
+        );
+        assert_eq!(parse("true").kind, ExprKind::Boolean(true));
+        assert_eq!(parse("'\\n'").kind, ExprKind::Char('\n'));
+        assert_eq!(
+            parse("\"a\\tb\"").kind,
+            ExprKind::String(Cow::Borrowed("a\tb"))
+        );
+        assert_eq!(parse("nullptr").node_type(), NodeType::Nullptr);
+        assert_eq!(parse("i8").kind, ExprKind::Type(PrimitiveType::I8));
+    }
+
+    #[test]
+    fn test_calls_index_and_arrays() {
+        assert_eq!(
+            parse("atan2(PI, TAU) + s.size()").to_string(),
+            "(atan2(PI, TAU) + (s.size()))"
+        );
+        assert_eq!(
+            parse("array1[-1 ^ 1].a").to_string(),
+            "(array1[((-1) ^ 1)].a)"
+        );
+        assert_eq!(parse("i8[][2]").node_type(), NodeType::Index);
+        assert_eq!(parse("i8[][2]").to_string(), "i8[][2]");
+        let array = parse("{Object(), {}}");
+        assert_eq!(array.node_type(), NodeType::Array);
+        assert_eq!(array.to_string(), "{Object(), {}}");
+    }
+
+    #[test]
+    fn test_spans() {
+        let input = "f(a, b)[1] * -x";
+        let expression = parse(input);
+        assert_eq!(expression.span.slice(input), input);
+        let ExprKind::Binary { left, right, .. } = &expression.kind else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(left.span.slice(input), "f(a, b)[1]");
+        assert_eq!(right.span.slice(input), "-x");
+        let ExprKind::Index { target, .. } = &left.kind else {
+            panic!("expected an index expression");
+        };
+        assert_eq!(target.span.slice(input), "f(a, b)");
+
+        let input = "-(a + b)";
+        let expression = parse(input);
+        assert_eq!(expression.span.slice(input), input);
+        let ExprKind::Unary { operand, .. } = &expression.kind else {
+            panic!("expected a unary expression");
+        };
+        assert_eq!(operand.span.slice(input), "a + b");
+        let input = "(a) + b";
+        let expression = parse(input);
+        assert_eq!(expression.span.slice(input), input);
+        let ExprKind::Binary { left, .. } = &expression.kind else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(left.span.slice(input), "a");
+        let input = "((a + b))";
+        assert_eq!(parse(input).span.slice(input), "a + b");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = parse_err("(1 + 2");
+        assert_eq!(error.kind, ParseErrorKind::UnclosedDelimiter(')'));
+        assert_eq!(error.span, Span::new(6, 6));
+        let error = parse_err("1 + * 2");
+        assert_eq!(error.kind, ParseErrorKind::ExpectedExpression);
+        assert_eq!(error.lexeme, "*");
+        assert_eq!(
+            parse_err("f(1 2)").kind,
+            ParseErrorKind::Expected {
+                expected: &["`,`", "`)`"],
+                context: Some("in argument list"),
+            }
+        );
+        assert_eq!(parse_err("a b").kind, ParseErrorKind::UnexpectedToken);
+        assert_eq!(parse_err("").kind, ParseErrorKind::ExpectedExpression);
+    }
+
+    #[test]
+    fn test_comments_are_skipped() {
+        assert_eq!(parse("1 /* uno */ + /// due\n 2").to_string(), "(1 + 2)");
+    }
+
+    #[test]
+    fn test_declarations() {
+        let input = "var obj1, obj2: Object = Object(), Derived()\nconst nume: u32 = 334 + #o3444 // c\nvar d: Derived";
+        let statements = parse_statements(input);
+        assert_eq!(statements.len(), 3);
+        let StmtKind::Declaration {
+            doc,
+            is_const,
+            names,
+            ty,
+            values,
+        } = &statements[0].kind
+        else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(*doc, None);
+        assert!(!is_const);
+        let names: Vec<&str> = names.iter().map(|name| name.name).collect();
+        assert_eq!(names, ["obj1", "obj2"]);
+        assert_eq!(ty.as_ref().unwrap().to_string(), "Object");
+        assert_eq!(values.len(), 2);
+        assert_eq!(
+            statements[0].span.slice(input),
+            "var obj1, obj2: Object = Object(), Derived()"
+        );
+        assert!(matches!(
+            statements[1].kind,
+            StmtKind::Declaration { is_const: true, .. }
+        ));
+        assert_eq!(
+            statements[1].span.slice(input),
+            "const nume: u32 = 334 + #o3444"
+        );
+        assert!(matches!(
+            &statements[2].kind,
+            StmtKind::Declaration { values, .. } if values.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let input = "/// text\nfun f() {}\n/**\n * Two\n * lines\n */\n// plain\nconst c: i8 = 1\n/// ignored\nx = 1\nvar v: i8";
+        let statements = parse_statements(input);
+        assert_eq!(statements.len(), 4);
+        assert!(matches!(
+            &statements[0].kind,
+            StmtKind::Function { doc: Some(doc), .. } if doc == "text"
+        ));
+        assert!(matches!(
+            &statements[1].kind,
+            StmtKind::Declaration { doc: Some(doc), .. } if doc == "Two\nlines"
+        ));
+        assert!(matches!(statements[2].kind, StmtKind::Assignment { .. }));
+        // A doc comment before anything but a declaration belongs to nothing.
+        assert!(matches!(
+            statements[3].kind,
+            StmtKind::Declaration { doc: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_function() {
+        let input = "fun funzione(num: i8[][2]): i8[2], Derived {\n\treturn {1, 2}, d\n}\nfun f() {\n\treturn\n}";
+        let statements = parse_statements(input);
+        let StmtKind::Function {
+            doc,
+            name,
+            params,
+            returns,
+            body,
+        } = &statements[0].kind
+        else {
+            panic!("expected a function");
+        };
+        assert_eq!(*doc, None);
+        assert_eq!(name.name, "funzione");
+        assert_eq!(params[0].name.name, "num");
+        assert_eq!(params[0].ty.to_string(), "i8[][2]");
+        assert_eq!(params[0].span.slice(input), "num: i8[][2]");
+        let returns = returns.as_ref().unwrap();
+        assert!(matches!(&returns.kind, TypeExprKind::Tuple(types) if types[0].is_array()));
+        assert_eq!(returns.to_string(), "i8[2], Derived");
+        assert_eq!(returns.span.slice(input), "i8[2], Derived");
+        assert!(matches!(&body.statements[0].kind, StmtKind::Return(values) if values.len() == 2));
+        let StmtKind::Function { body, returns, .. } = &statements[1].kind else {
+            panic!("expected a function");
+        };
+        assert!(returns.is_none());
+        assert!(matches!(&body.statements[0].kind, StmtKind::Return(values) if values.is_empty()));
+    }
+
+    #[test]
+    fn test_types() {
+        let types = |input: &str| -> Vec<String> {
+            parse_statements(input)
+                .iter()
+                .filter_map(|statement| match &statement.kind {
+                    StmtKind::Declaration { ty, .. } => ty.as_ref().map(|ty| ty.to_string()),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            types("var arg: i8[][2]\nvar a, b: Derived[2]\nvar c, d: Object[] = {}, {}\nconst e: c64 = 1i"),
+            ["i8[][2]", "Derived[2]", "Object[]", "c64"]
+        );
+        let input = "var arg: i8[ ][2]";
+        let statements = parse_statements(input);
+        let StmtKind::Declaration { ty: Some(ty), .. } = &statements[0].kind else {
+            panic!("expected a typed declaration");
+        };
+        assert_eq!(ty.span.slice(input), "i8[ ][2]");
+        let TypeExprKind::Array {
+            element,
+            dimensions,
+        } = &ty.kind
+        else {
+            panic!("expected an array type");
+        };
+        assert_eq!(element.kind, TypeExprKind::Primitive(PrimitiveType::I8));
+        assert_eq!(dimensions, &[None, Some(2)]);
+        assert_eq!(
+            first_error("var a: i8[n]").message(),
+            "expected array size or `]`"
+        );
+        assert_eq!(
+            first_error("var a: i8[1i]").message(),
+            "expected array size or `]`"
+        );
+        assert_eq!(first_error("var a: 3").message(), "expected type");
+    }
+
+    #[test]
+    fn test_control_flow() {
+        let input = "main {\n\tif(num == 2) {\n\t\tbreak\n\t} else if(num % 2 == 0) {\n\t} else {\n\t\tcontinue\n\t}\n\twhile(false) {}\n\tfor var i: i8 = 45.43 ^ (num / num1), -100, -1 {}\n\tfor num = 1, 10 {}\n}";
+        let statements = parse_statements(input);
+        let StmtKind::Main(body) = &statements[0].kind else {
+            panic!("expected main");
+        };
+        assert_eq!(body.span, Span::new(5, input.len()));
+        assert_eq!(statements[0].span, Span::new(0, input.len()));
+        let StmtKind::If {
+            condition,
+            else_branch: Some(ElseBranch::If(else_if)),
+            ..
+        } = &body.statements[0].kind
+        else {
+            panic!("expected an if with an else if");
+        };
+        assert_eq!(condition.to_string(), "(num == 2)");
+        assert!(matches!(
+            else_if.kind,
+            StmtKind::If {
+                else_branch: Some(ElseBranch::Block(_)),
+                ..
+            }
+        ));
+        assert!(matches!(body.statements[1].kind, StmtKind::While { .. }));
+        let StmtKind::For {
+            init, end, step, ..
+        } = &body.statements[2].kind
+        else {
+            panic!("expected a for");
+        };
+        assert!(matches!(init.kind, StmtKind::Declaration { .. }));
+        assert_eq!(init.span.slice(input), "var i: i8 = 45.43 ^ (num / num1)");
+        assert_eq!(end.to_string(), "(-100)");
+        assert_eq!(step.as_ref().unwrap().to_string(), "(-1)");
+        assert!(matches!(
+            &body.statements[3].kind,
+            StmtKind::For { init, step: None, .. } if matches!(init.kind, StmtKind::Assignment { .. })
+        ));
+    }
+
+    #[test]
+    fn test_assignments() {
+        let statements = parse_statements(
+            "obj1, obj2 = obj2, obj1\nnum1 -= num / 1.5\narray1[-1].a = 45\nexit(0)",
+        );
+        assert!(matches!(
+            &statements[0].kind,
+            StmtKind::Assignment { targets, op: BinaryOp::Assign, values } if targets.len() == 2 && values.len() == 2
+        ));
+        assert!(matches!(
+            statements[1].kind,
+            StmtKind::Assignment {
+                op: BinaryOp::SubAssign,
+                ..
+            }
+        ));
+        let StmtKind::Assignment { targets, .. } = &statements[2].kind else {
+            panic!("expected an assignment");
+        };
+        assert_eq!(targets[0].to_string(), "(array1[(-1)].a)");
+        assert!(matches!(statements[3].kind, StmtKind::Expression(_)));
+    }
+
+    #[test]
+    fn test_statement_errors() {
+        let (statements, errors) = Parser::new("test.vn", "var a: i8 = 1 var b: i8 = 2").parse();
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedToken);
+        assert_eq!(errors[0].lexeme, "var");
+        assert!(matches!(statements[0].kind, StmtKind::Declaration { .. }));
+        assert_eq!(
+            first_error("var a").message(),
+            "expected `:` or `=` after variable name"
+        );
+        assert_eq!(
+            first_error("main {\n\tbreak\n"),
+            ParseErrorKind::UnclosedDelimiter('}')
+        );
+        assert_eq!(
+            first_error("a, b").message(),
+            "expected `=` after the assignment targets"
+        );
+        assert_eq!(
+            first_error("fun f(a: i8 b: i8) {}").message(),
+            "expected `,` or `)` in parameter list"
+        );
+        assert_eq!(
+            first_error("fun f() i8 {}").message(),
+            "expected `:` or `{` after parameter list"
+        );
+        assert_eq!(
+            first_error("if(a) {} else b").message(),
+            "expected `if` or `{` after `else`"
+        );
+        assert_eq!(
+            first_error("while(a) b").message(),
+            "expected `{` after the loop condition"
+        );
+        assert_eq!(
+            first_error("for var i = 0, 1 {}").message(),
+            "expected `:` after loop variable name"
+        );
+    }
+
+    #[test]
+    fn test_recovery_reports_every_error() {
+        let input = "var a\nvar b: i8 = 1\nfun f(: i8 {\n\treturn 1\n}\nmain {\n\tx = 1 + * 2\n\tprintln(\"ok\")\n}\nvar c: i8 = f(1, *, 3)";
+        let (statements, errors) = Parser::new("test.vn", input).parse();
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|error| error.to_diagnostic().to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "error[P0004]: expected `:` or `=` after variable name",
+                "error[P0004]: expected parameter name or `)`",
+                "error[P0001]: expected expression",
+                "error[P0001]: expected expression",
+            ]
+        );
+        let kinds: Vec<NodeType> = statements
+            .iter()
+            .map(|statement| statement.node_type())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                NodeType::Error,
+                NodeType::Statement,
+                NodeType::Error,
+                NodeType::Statement,
+                NodeType::Statement,
+            ]
+        );
+        assert_eq!(
+            statements[2].span.slice(input),
+            "fun f(: i8 {\n\treturn 1\n}"
+        );
+        let StmtKind::Main(body) = &statements[3].kind else {
+            panic!("expected main");
+        };
+        assert_eq!(body.statements[0].kind, StmtKind::Error);
+        assert!(matches!(body.statements[1].kind, StmtKind::Expression(_)));
+        let StmtKind::Declaration { values, .. } = &statements[4].kind else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(values[0].to_string(), "f(1, <error>, 3)");
+
+        // The `)` closing the nested parentheses is not the end of the argument list.
+        let input = "var c: i8 = f((1 + ), 3)";
+        let (statements, errors) = Parser::new("test.vn", input).parse();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ParseErrorKind::ExpectedExpression);
+        let StmtKind::Declaration { values, .. } = &statements[0].kind else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(values[0].to_string(), "f(<error>, 3)");
+        let ExprKind::Call { args, .. } = &values[0].kind else {
+            panic!("expected a call");
+        };
+        assert_eq!(args[0].span.slice(input), "(1 + )");
+    }
+
+    #[test]
+    fn test_recovery_always_makes_progress() {
+        let (statements, errors) = Parser::new("test.vn", "}\n)\nvar a: i8 = 1").parse();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(statements.len(), 3);
+        assert!(matches!(statements[2].kind, StmtKind::Declaration { .. }));
+        let (statements, errors) = Parser::new("test.vn", "main {\n\tvar a: i8 = 1\n").parse();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(statements[0].kind, StmtKind::Error);
+    }
+
+    #[test]
+    fn test_parse_input_file() {
+        let statements = parse_statements(include_str!("../../../input.vn"));
+        assert_eq!(statements.len(), 10);
+        assert!(matches!(statements[9].kind, StmtKind::Main(_)));
+    }
+}
@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+
+use super::ast::{BinaryOp, Expr};
+use super::node_type::NodeType;
+use super::type_expr::TypeExpr;
+use crate::token::Span;
+
+/// A name being declared, with its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ident<'src> {
+    pub name: &'src str,
+    pub span: Span,
+}
+
+impl<'src> Ident<'src> {
+    pub fn new(name: &'src str, span: Span) -> Self {
+        Self { name, span }
+    }
+}
+
+/// `name: type` in a function signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param<'src> {
+    pub name: Ident<'src>,
+    pub ty: TypeExpr<'src>,
+    pub span: Span,
+}
+
+/// Statements between braces; `span` includes the braces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block<'src> {
+    pub statements: Vec<Stmt<'src>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElseBranch<'src> {
+    /// `else if`, always a `StmtKind::If`.
+    If(Box<Stmt<'src>>),
+    Block(Block<'src>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind<'src> {
+    /// `var a, b: T = x, y` or `const c: T = x`; either the type or the values may be missing.
+    Declaration {
+        /// Doc comments right before the declaration, one line each.
+        doc: Option<Cow<'src, str>>,
+        is_const: bool,
+        names: Vec<Ident<'src>>,
+        ty: Option<TypeExpr<'src>>,
+        values: Vec<Expr<'src>>,
+    },
+    /// `a, b = b, a`, also with compound operators like `a += 1`.
+    Assignment {
+        targets: Vec<Expr<'src>>,
+        op: BinaryOp,
+        values: Vec<Expr<'src>>,
+    },
+    Expression(Expr<'src>),
+    Main(Block<'src>),
+    Function {
+        doc: Option<Cow<'src, str>>,
+        name: Ident<'src>,
+        params: Vec<Param<'src>>,
+        /// A `TypeExprKind::Tuple` when the function returns more than one value.
+        returns: Option<TypeExpr<'src>>,
+        body: Block<'src>,
+    },
+    If {
+        condition: Expr<'src>,
+        then_block: Block<'src>,
+        else_branch: Option<ElseBranch<'src>>,
+    },
+    While {
+        condition: Expr<'src>,
+        body: Block<'src>,
+    },
+    /// `for init, end, step { }` where `init` is a declaration of one variable or an assignment
+    /// to it.
+    For {
+        init: Box<Stmt<'src>>,
+        end: Expr<'src>,
+        step: Option<Expr<'src>>,
+        body: Block<'src>,
+    },
+    Break,
+    Continue,
+    Return(Vec<Expr<'src>>),
+    /// A statement that failed to parse, spanning the tokens skipped to recover.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt<'src> {
+    pub kind: StmtKind<'src>,
+    pub span: Span,
+}
+
+impl<'src> Stmt<'src> {
+    pub fn new(kind: StmtKind<'src>, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn node_type(&self) -> NodeType {
+        match self.kind {
+            StmtKind::Error => NodeType::Error,
+            _ => NodeType::Statement,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+
+use crate::token::{Span, TokenType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrimitiveType {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    C32,
+    C64,
+    Char,
+    String,
+    Bool,
+}
+
+impl PrimitiveType {
+    /// The primitive named by a `TokenType::TypeI8..=TypeBool` token.
+    pub fn from_token_type(token_type: &TokenType) -> Option<Self> {
+        let primitive = match token_type {
+            TokenType::TypeI8 => PrimitiveType::I8,
+            TokenType::TypeI16 => PrimitiveType::I16,
+            TokenType::TypeI32 => PrimitiveType::I32,
+            TokenType::TypeI64 => PrimitiveType::I64,
+            TokenType::TypeU8 => PrimitiveType::U8,
+            TokenType::TypeU16 => PrimitiveType::U16,
+            TokenType::TypeU32 => PrimitiveType::U32,
+            TokenType::TypeU64 => PrimitiveType::U64,
+            TokenType::TypeF32 => PrimitiveType::F32,
+            TokenType::TypeF64 => PrimitiveType::F64,
+            TokenType::TypeC32 => PrimitiveType::C32,
+            TokenType::TypeC64 => PrimitiveType::C64,
+            TokenType::TypeChar => PrimitiveType::Char,
+            TokenType::TypeString => PrimitiveType::String,
+            TokenType::TypeBool => PrimitiveType::Bool,
+            _ => return None,
+        };
+        Some(primitive)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrimitiveType::I8 => "i8",
+            PrimitiveType::I16 => "i16",
+            PrimitiveType::I32 => "i32",
+            PrimitiveType::I64 => "i64",
+            PrimitiveType::U8 => "u8",
+            PrimitiveType::U16 => "u16",
+            PrimitiveType::U32 => "u32",
+            PrimitiveType::U64 => "u64",
+            PrimitiveType::F32 => "f32",
+            PrimitiveType::F64 => "f64",
+            PrimitiveType::C32 => "c32",
+            PrimitiveType::C64 => "c64",
+            PrimitiveType::Char => "char",
+            PrimitiveType::String => "string",
+            PrimitiveType::Bool => "bool",
+        }
+    }
+}
+
+impl fmt::Display for PrimitiveType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeExprKind<'src> {
+    Primitive(PrimitiveType),
+    /// A user type such as `Object` or `Derived`.
+    Named(&'src str),
+    /// `element[]..[n]`, one entry per dimension in source order; `None` when the size is not
+    /// given.
+    Array {
+        element: Box<TypeExpr<'src>>,
+        dimensions: Vec<Option<u64>>,
+    },
+    /// Return types of a function with more than one, `i8[2], Derived`.
+    Tuple(Vec<TypeExpr<'src>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeExpr<'src> {
+    pub kind: TypeExprKind<'src>,
+    pub span: Span,
+}
+
+impl<'src> TypeExpr<'src> {
+    pub fn new(kind: TypeExprKind<'src>, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    /// A single type stays as it is, more become a `Tuple`; `None` when `types` is empty.
+    pub fn from_returns(mut types: Vec<TypeExpr<'src>>) -> Option<Self> {
+        match types.len() {
+            0 => None,
+            1 => types.pop(),
+            _ => {
+                let span = types[0].span.to(types[types.len() - 1].span);
+                Some(Self::new(TypeExprKind::Tuple(types), span))
+            }
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self.kind, TypeExprKind::Array { .. })
+    }
+}
+
+/// Prints the type the way it is written in source, e.g. `i8[][2]` or `i8[2], Derived`.
+impl fmt::Display for TypeExpr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TypeExprKind::Primitive(primitive) => write!(f, "{}", primitive),
+            TypeExprKind::Named(name) => write!(f, "{}", name),
+            TypeExprKind::Array {
+                element,
+                dimensions,
+            } => {
+                write!(f, "{}", element)?;
+                for dimension in dimensions {
+                    match dimension {
+                        Some(size) => write!(f, "[{}]", size)?,
+                        None => write!(f, "[]")?,
+                    }
+                }
+                Ok(())
+            }
+            TypeExprKind::Tuple(types) => {
+                for (position, ty) in types.iter().enumerate() {
+                    if position > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ty)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_from_token_type() {
+        assert_eq!(
+            PrimitiveType::from_token_type(&TokenType::TypeC64),
+            Some(PrimitiveType::C64)
+        );
+        assert_eq!(PrimitiveType::from_token_type(&TokenType::Identifier), None);
+        assert_eq!(PrimitiveType::String.to_string(), "string");
+    }
+
+    #[test]
+    fn test_display() {
+        let i8_type = TypeExpr::new(TypeExprKind::Primitive(PrimitiveType::I8), Span::new(0, 2));
+        let array = TypeExpr::new(
+            TypeExprKind::Array {
+                element: Box::new(i8_type),
+                dimensions: vec![None, Some(2)],
+            },
+            Span::new(0, 7),
+        );
+        assert_eq!(array.to_string(), "i8[][2]");
+        let derived = TypeExpr::new(TypeExprKind::Named("Derived"), Span::new(9, 16));
+        let returns = TypeExpr::from_returns(vec![array, derived]).unwrap();
+        assert_eq!(returns.span, Span::new(0, 16));
+        assert_eq!(returns.to_string(), "i8[][2], Derived");
+        assert_eq!(TypeExpr::from_returns(Vec::new()), None);
+    }
+}
@@ -0,0 +1,276 @@
+use super::ast::{Expr, ExprKind};
+use super::stmt::{Block, ElseBranch, Ident, Param, Stmt, StmtKind};
+use super::type_expr::{TypeExpr, TypeExprKind};
+
+/// Read-only traversal of the AST. Every hook defaults to the matching `walk_*` function, which
+/// visits the children in source order; override a hook to act on a node and call the `walk_*`
+/// function from it to keep descending.
+///
+/// Nodes are borrowed for `'ast`, so a visitor can keep references to the nodes it sees.
+pub trait Visitor<'ast, 'src> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt<'src>) {
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr<'src>) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_block(&mut self, block: &'ast Block<'src>) {
+        walk_block(self, block);
+    }
+
+    fn visit_else_branch(&mut self, else_branch: &'ast ElseBranch<'src>) {
+        walk_else_branch(self, else_branch);
+    }
+
+    fn visit_param(&mut self, param: &'ast Param<'src>) {
+        walk_param(self, param);
+    }
+
+    fn visit_type_expr(&mut self, ty: &'ast TypeExpr<'src>) {
+        walk_type_expr(self, ty);
+    }
+
+    fn visit_ident(&mut self, _ident: &'ast Ident<'src>) {}
+}
+
+pub fn walk_stmts<'ast, 'src, V: Visitor<'ast, 'src> + ?Sized>(
+    visitor: &mut V,
+    statements: &'ast [Stmt<'src>],
+) {
+    for statement in statements {
+        visitor.visit_stmt(statement);
+    }
+}
+
+pub fn walk_stmt<'ast, 'src, V: Visitor<'ast, 'src> + ?Sized>(
+    visitor: &mut V,
+    stmt: &'ast Stmt<'src>,
+) {
+    match &stmt.kind {
+        StmtKind::Declaration {
+            names, ty, values, ..
+        } => {
+            for name in names {
+                visitor.visit_ident(name);
+            }
+            if let Some(ty) = ty {
+                visitor.visit_type_expr(ty);
+            }
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        StmtKind::Assignment {
+            targets, values, ..
+        } => {
+            for target in targets {
+                visitor.visit_expr(target);
+            }
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        StmtKind::Expression(expr) => visitor.visit_expr(expr),
+        StmtKind::Main(body) => visitor.visit_block(body),
+        StmtKind::Function {
+            name,
+            params,
+            returns,
+            body,
+            ..
+        } => {
+            visitor.visit_ident(name);
+            for param in params {
+                visitor.visit_param(param);
+            }
+            if let Some(returns) = returns {
+                visitor.visit_type_expr(returns);
+            }
+            visitor.visit_block(body);
+        }
+        StmtKind::If {
+            condition,
+            then_block,
+            else_branch,
+        } => {
+            visitor.visit_expr(condition);
+            visitor.visit_block(then_block);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_else_branch(else_branch);
+            }
+        }
+        StmtKind::While { condition, body } => {
+            visitor.visit_expr(condition);
+            visitor.visit_block(body);
+        }
+        StmtKind::For {
+            init,
+            end,
+            step,
+            body,
+        } => {
+            visitor.visit_stmt(init);
+            visitor.visit_expr(end);
+            if let Some(step) = step {
+                visitor.visit_expr(step);
+            }
+            visitor.visit_block(body);
+        }
+        StmtKind::Return(values) => {
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        StmtKind::Break | StmtKind::Continue | StmtKind::Error => {}
+    }
+}
+
+pub fn walk_expr<'ast, 'src, V: Visitor<'ast, 'src> + ?Sized>(
+    visitor: &mut V,
+    expr: &'ast Expr<'src>,
+) {
+    match &expr.kind {
+        ExprKind::Binary { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        ExprKind::Unary { operand, .. } => visitor.visit_expr(operand),
+        ExprKind::Index { target, index } => {
+            visitor.visit_expr(target);
+            if let Some(index) = index {
+                visitor.visit_expr(index);
+            }
+        }
+        ExprKind::Array(elements) => {
+            for element in elements {
+                visitor.visit_expr(element);
+            }
+        }
+        ExprKind::Call { callee, args } => {
+            visitor.visit_expr(callee);
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+        ExprKind::Number(_)
+        | ExprKind::Boolean(_)
+        | ExprKind::Char(_)
+        | ExprKind::String(_)
+        | ExprKind::Variable(_)
+        | ExprKind::Nullptr
+        | ExprKind::Type(_)
+        | ExprKind::Error => {}
+    }
+}
+
+pub fn walk_block<'ast, 'src, V: Visitor<'ast, 'src> + ?Sized>(
+    visitor: &mut V,
+    block: &'ast Block<'src>,
+) {
+    walk_stmts(visitor, &block.statements);
+}
+
+pub fn walk_else_branch<'ast, 'src, V: Visitor<'ast, 'src> + ?Sized>(
+    visitor: &mut V,
+    else_branch: &'ast ElseBranch<'src>,
+) {
+    match else_branch {
+        ElseBranch::If(stmt) => visitor.visit_stmt(stmt),
+        ElseBranch::Block(block) => visitor.visit_block(block),
+    }
+}
+
+pub fn walk_param<'ast, 'src, V: Visitor<'ast, 'src> + ?Sized>(
+    visitor: &mut V,
+    param: &'ast Param<'src>,
+) {
+    visitor.visit_ident(&param.name);
+    visitor.visit_type_expr(&param.ty);
+}
+
+pub fn walk_type_expr<'ast, 'src, V: Visitor<'ast, 'src> + ?Sized>(
+    visitor: &mut V,
+    ty: &'ast TypeExpr<'src>,
+) {
+    match &ty.kind {
+        TypeExprKind::Array { element, .. } => visitor.visit_type_expr(element),
+        TypeExprKind::Tuple(types) => {
+            for ty in types {
+                visitor.visit_type_expr(ty);
+            }
+        }
+        TypeExprKind::Primitive(_) | TypeExprKind::Named(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    /// Collects declared names and called functions.
+    #[derive(Default)]
+    struct Names<'src> {
+        declared: Vec<&'src str>,
+        called: Vec<String>,
+    }
+
+    impl<'ast, 'src> Visitor<'ast, 'src> for Names<'src> {
+        fn visit_expr(&mut self, expr: &'ast Expr<'src>) {
+            if let ExprKind::Call { callee, .. } = &expr.kind {
+                self.called.push(callee.to_string());
+            }
+            walk_expr(self, expr);
+        }
+
+        fn visit_ident(&mut self, ident: &'ast Ident<'src>) {
+            self.declared.push(ident.name);
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        let input = "fun f(a: i8): i8 {\n\treturn g(a)\n}\nmain {\n\tfor var i: i8 = 0, 10 {\n\t\tif(i > 2) {} else if(h(i)) {}\n\t}\n\tprintln(f(1))\n}";
+        let (statements, errors) = Parser::new("test.vn", input).parse();
+        assert!(errors.is_empty());
+        let mut names = Names::default();
+        walk_stmts(&mut names, &statements);
+        assert_eq!(names.declared, ["f", "a", "i"]);
+        assert_eq!(names.called, ["g", "h", "println", "f"]);
+    }
+
+    /// Keeps the outermost type of each annotation and the kind of each `else`.
+    #[derive(Default)]
+    struct Types<'ast, 'src> {
+        types: Vec<&'ast TypeExpr<'src>>,
+        else_branches: Vec<&'static str>,
+    }
+
+    impl<'ast, 'src> Visitor<'ast, 'src> for Types<'ast, 'src> {
+        fn visit_type_expr(&mut self, ty: &'ast TypeExpr<'src>) {
+            self.types.push(ty);
+        }
+
+        fn visit_else_branch(&mut self, else_branch: &'ast ElseBranch<'src>) {
+            self.else_branches.push(match else_branch {
+                ElseBranch::If(_) => "else if",
+                ElseBranch::Block(_) => "else",
+            });
+            walk_else_branch(self, else_branch);
+        }
+    }
+
+    #[test]
+    fn test_visitor_type_and_else_hooks() {
+        let input = "fun f(a: i8[2], b: Derived): f64 {\n\tif(a) {} else if(b) {} else {\n\t\tvar c: bool[] = {}\n\t}\n}";
+        let (statements, errors) = Parser::new("test.vn", input).parse();
+        assert!(errors.is_empty());
+        let mut types = Types::default();
+        walk_stmts(&mut types, &statements);
+        let spans: Vec<&str> = types.types.iter().map(|ty| ty.span.slice(input)).collect();
+        assert_eq!(spans, ["i8[2]", "Derived", "f64", "bool[]"]);
+        assert_eq!(types.else_branches, ["else if", "else"]);
+    }
+}
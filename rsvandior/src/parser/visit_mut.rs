@@ -0,0 +1,291 @@
+use super::ast::{Expr, ExprKind};
+use super::stmt::{Block, ElseBranch, Ident, Param, Stmt, StmtKind};
+use super::type_expr::{TypeExpr, TypeExprKind};
+
+/// Like [`Visitor`](super::visit::Visitor), but the hooks get mutable access so nodes can be
+/// edited in place.
+pub trait VisitorMut<'src> {
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt<'src>) {
+        walk_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr<'src>) {
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block<'src>) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_else_branch_mut(&mut self, else_branch: &mut ElseBranch<'src>) {
+        walk_else_branch_mut(self, else_branch);
+    }
+
+    fn visit_param_mut(&mut self, param: &mut Param<'src>) {
+        walk_param_mut(self, param);
+    }
+
+    fn visit_type_expr_mut(&mut self, ty: &mut TypeExpr<'src>) {
+        walk_type_expr_mut(self, ty);
+    }
+
+    fn visit_ident_mut(&mut self, _ident: &mut Ident<'src>) {}
+}
+
+pub fn walk_stmts_mut<'src, V: VisitorMut<'src> + ?Sized>(
+    visitor: &mut V,
+    statements: &mut [Stmt<'src>],
+) {
+    for statement in statements {
+        visitor.visit_stmt_mut(statement);
+    }
+}
+
+pub fn walk_stmt_mut<'src, V: VisitorMut<'src> + ?Sized>(visitor: &mut V, stmt: &mut Stmt<'src>) {
+    match &mut stmt.kind {
+        StmtKind::Declaration {
+            names, ty, values, ..
+        } => {
+            for name in names {
+                visitor.visit_ident_mut(name);
+            }
+            if let Some(ty) = ty {
+                visitor.visit_type_expr_mut(ty);
+            }
+            for value in values {
+                visitor.visit_expr_mut(value);
+            }
+        }
+        StmtKind::Assignment {
+            targets, values, ..
+        } => {
+            for target in targets {
+                visitor.visit_expr_mut(target);
+            }
+            for value in values {
+                visitor.visit_expr_mut(value);
+            }
+        }
+        StmtKind::Expression(expr) => visitor.visit_expr_mut(expr),
+        StmtKind::Main(body) => visitor.visit_block_mut(body),
+        StmtKind::Function {
+            name,
+            params,
+            returns,
+            body,
+            ..
+        } => {
+            visitor.visit_ident_mut(name);
+            for param in params {
+                visitor.visit_param_mut(param);
+            }
+            if let Some(returns) = returns {
+                visitor.visit_type_expr_mut(returns);
+            }
+            visitor.visit_block_mut(body);
+        }
+        StmtKind::If {
+            condition,
+            then_block,
+            else_branch,
+        } => {
+            visitor.visit_expr_mut(condition);
+            visitor.visit_block_mut(then_block);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_else_branch_mut(else_branch);
+            }
+        }
+        StmtKind::While { condition, body } => {
+            visitor.visit_expr_mut(condition);
+            visitor.visit_block_mut(body);
+        }
+        StmtKind::For {
+            init,
+            end,
+            step,
+            body,
+        } => {
+            visitor.visit_stmt_mut(init);
+            visitor.visit_expr_mut(end);
+            if let Some(step) = step {
+                visitor.visit_expr_mut(step);
+            }
+            visitor.visit_block_mut(body);
+        }
+        StmtKind::Return(values) => {
+            for value in values {
+                visitor.visit_expr_mut(value);
+            }
+        }
+        StmtKind::Break | StmtKind::Continue | StmtKind::Error => {}
+    }
+}
+
+pub fn walk_expr_mut<'src, V: VisitorMut<'src> + ?Sized>(visitor: &mut V, expr: &mut Expr<'src>) {
+    match &mut expr.kind {
+        ExprKind::Binary { left, right, .. } => {
+            visitor.visit_expr_mut(left);
+            visitor.visit_expr_mut(right);
+        }
+        ExprKind::Unary { operand, .. } => visitor.visit_expr_mut(operand),
+        ExprKind::Index { target, index } => {
+            visitor.visit_expr_mut(target);
+            if let Some(index) = index {
+                visitor.visit_expr_mut(index);
+            }
+        }
+        ExprKind::Array(elements) => {
+            for element in elements {
+                visitor.visit_expr_mut(element);
+            }
+        }
+        ExprKind::Call { callee, args } => {
+            visitor.visit_expr_mut(callee);
+            for arg in args {
+                visitor.visit_expr_mut(arg);
+            }
+        }
+        ExprKind::Number(_)
+        | ExprKind::Boolean(_)
+        | ExprKind::Char(_)
+        | ExprKind::String(_)
+        | ExprKind::Variable(_)
+        | ExprKind::Nullptr
+        | ExprKind::Type(_)
+        | ExprKind::Error => {}
+    }
+}
+
+pub fn walk_block_mut<'src, V: VisitorMut<'src> + ?Sized>(
+    visitor: &mut V,
+    block: &mut Block<'src>,
+) {
+    walk_stmts_mut(visitor, &mut block.statements);
+}
+
+pub fn walk_else_branch_mut<'src, V: VisitorMut<'src> + ?Sized>(
+    visitor: &mut V,
+    else_branch: &mut ElseBranch<'src>,
+) {
+    match else_branch {
+        ElseBranch::If(stmt) => visitor.visit_stmt_mut(stmt),
+        ElseBranch::Block(block) => visitor.visit_block_mut(block),
+    }
+}
+
+pub fn walk_param_mut<'src, V: VisitorMut<'src> + ?Sized>(
+    visitor: &mut V,
+    param: &mut Param<'src>,
+) {
+    visitor.visit_ident_mut(&mut param.name);
+    visitor.visit_type_expr_mut(&mut param.ty);
+}
+
+pub fn walk_type_expr_mut<'src, V: VisitorMut<'src> + ?Sized>(
+    visitor: &mut V,
+    ty: &mut TypeExpr<'src>,
+) {
+    match &mut ty.kind {
+        TypeExprKind::Array { element, .. } => visitor.visit_type_expr_mut(element),
+        TypeExprKind::Tuple(types) => {
+            for ty in types {
+                visitor.visit_type_expr_mut(ty);
+            }
+        }
+        TypeExprKind::Primitive(_) | TypeExprKind::Named(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    /// Renames every use and declaration of a variable.
+    struct Rename<'src> {
+        from: &'src str,
+        to: &'src str,
+    }
+
+    impl<'src> VisitorMut<'src> for Rename<'src> {
+        fn visit_expr_mut(&mut self, expr: &mut Expr<'src>) {
+            if expr.kind == ExprKind::Variable(self.from) {
+                expr.kind = ExprKind::Variable(self.to);
+            }
+            walk_expr_mut(self, expr);
+        }
+
+        fn visit_ident_mut(&mut self, ident: &mut Ident<'src>) {
+            if ident.name == self.from {
+                ident.name = self.to;
+            }
+        }
+    }
+
+    #[test]
+    fn test_visitor_mut() {
+        let input = "fun f(a: i8): i8 {\n\tvar b: i8 = a * 2\n\treturn a + b\n}";
+        let (mut statements, errors) = Parser::new("test.vn", input).parse();
+        assert!(errors.is_empty());
+        walk_stmts_mut(&mut Rename { from: "a", to: "x" }, &mut statements);
+        let StmtKind::Function { params, body, .. } = &statements[0].kind else {
+            panic!("expected a function");
+        };
+        assert_eq!(params[0].name.name, "x");
+        assert_eq!(params[0].name.span.slice(input), "a");
+        let StmtKind::Declaration { values, .. } = &body.statements[0].kind else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(values[0].to_string(), "(x * 2)");
+        let StmtKind::Return(values) = &body.statements[1].kind else {
+            panic!("expected a return");
+        };
+        assert_eq!(values[0].to_string(), "(x + b)");
+        assert_eq!(values[0].span.slice(input), "a + b");
+    }
+
+    /// Replaces a user type with another, also inside arrays.
+    struct RenameType<'src> {
+        from: &'src str,
+        to: &'src str,
+    }
+
+    impl<'src> VisitorMut<'src> for RenameType<'src> {
+        fn visit_type_expr_mut(&mut self, ty: &mut TypeExpr<'src>) {
+            if ty.kind == TypeExprKind::Named(self.from) {
+                ty.kind = TypeExprKind::Named(self.to);
+            }
+            walk_type_expr_mut(self, ty);
+        }
+    }
+
+    #[test]
+    fn test_visitor_mut_type_hook() {
+        let input = "fun f(a: Object[]): Object, i8 {\n\tvar b: Object = a[0]\n}";
+        let (mut statements, errors) = Parser::new("test.vn", input).parse();
+        assert!(errors.is_empty());
+        walk_stmts_mut(
+            &mut RenameType {
+                from: "Object",
+                to: "Derived",
+            },
+            &mut statements,
+        );
+        let StmtKind::Function {
+            params,
+            returns,
+            body,
+            ..
+        } = &statements[0].kind
+        else {
+            panic!("expected a function");
+        };
+        assert_eq!(params[0].ty.to_string(), "Derived[]");
+        assert_eq!(params[0].ty.span.slice(input), "Object[]");
+        assert_eq!(returns.as_ref().unwrap().to_string(), "Derived, i8");
+        let StmtKind::Declaration { ty, .. } = &body.statements[0].kind else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(ty.as_ref().unwrap().to_string(), "Derived");
+    }
+}
@@ -0,0 +1,7 @@
+/// Side a chain of operators with the same precedence groups from: `a - b - c` is
+/// `(a - b) - c`, `a = b = c` is `a = (b = c)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    Left,
+    Right,
+}
@@ -1,31 +1,46 @@
-use std::{
-    ffi::{CStr, CString},
-    fmt,
-};
+use std::fmt;
 
-#[repr(C)]
-#[derive(Debug, Clone)]
+use super::file_id::FileId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CodeSourceLocation {
-    pub file_name: *const std::os::raw::c_char,
+    pub file_id: FileId,
     pub line: usize,
     pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
 }
 
 impl CodeSourceLocation {
-    pub fn new(file_name: String, line: usize, column: usize) -> Self {
+    pub fn new(file_id: FileId, line: usize, column: usize) -> Self {
+        Self::new_with_end(file_id, line, column, line, column)
+    }
+
+    pub fn new_with_end(
+        file_id: FileId,
+        line: usize,
+        column: usize,
+        end_line: usize,
+        end_column: usize,
+    ) -> Self {
         Self {
-            file_name: CString::new(file_name).unwrap().into_raw(),
+            file_id,
             line,
             column,
+            end_line,
+            end_column,
         }
     }
 
+    pub fn file_name(&self) -> std::sync::Arc<str> {
+        self.file_id.name()
+    }
+
     pub fn to_compact_string(&self) -> String {
-        let c_str1 = unsafe { CStr::from_ptr(self.file_name) };
-        let file_name_str = c_str1.to_str().unwrap_or("Unknown");
         format!(
             "(fn: {}, ln: {}, cln: {})",
-            file_name_str, self.line, self.column
+            self.file_id, self.line, self.column
         )
     }
 }
@@ -36,13 +51,11 @@ impl fmt::Display for CodeSourceLocation {
             // Usa il formato compatto con "{:#}".
             write!(f, "{}", self.to_compact_string())
         } else {
-            let c_str1 = unsafe { CStr::from_ptr(self.file_name) };
-            let file_name_str = c_str1.to_str().unwrap_or("Unknown");
             // Usa il formato standard.
             write!(
                 f,
                 "(file: {},line: {}, column: {})",
-                file_name_str, self.line, self.column
+                self.file_id, self.line, self.column
             )
         }
     }
@@ -54,23 +67,33 @@ mod tests {
 
     #[test]
     fn test_new_code_source_location() {
-        let location = CodeSourceLocation::new("main.rs".to_string(), 10, 20);
-        let c_str = unsafe { CStr::from_ptr(location.file_name) };
-        assert_eq!(c_str.to_str().unwrap(), "main.rs");
+        let location = CodeSourceLocation::new(FileId::intern("main.rs"), 10, 20);
+        assert_eq!(&*location.file_name(), "main.rs");
         assert_eq!(location.line, 10);
         assert_eq!(location.column, 20);
+        assert_eq!(location.end_line, 10);
+        assert_eq!(location.end_column, 20);
+    }
+
+    #[test]
+    fn test_new_with_end_code_source_location() {
+        let location = CodeSourceLocation::new_with_end(FileId::intern("main.rs"), 1, 4, 2, 3);
+        assert_eq!(location.line, 1);
+        assert_eq!(location.column, 4);
+        assert_eq!(location.end_line, 2);
+        assert_eq!(location.end_column, 3);
     }
 
     #[test]
     fn test_to_compact_string() {
-        let location = CodeSourceLocation::new("lib.rs".to_string(), 5, 15);
+        let location = CodeSourceLocation::new(FileId::intern("lib.rs"), 5, 15);
         let compact_str = location.to_compact_string();
         assert_eq!(compact_str, "(fn: lib.rs, ln: 5, cln: 15)");
     }
 
     #[test]
     fn test_display() {
-        let location = CodeSourceLocation::new("mod.rs".to_string(), 3, 8);
+        let location = CodeSourceLocation::new(FileId::intern("mod.rs"), 3, 8);
         let display_str = format!("{}", location);
         assert_eq!(display_str, "(file: mod.rs,line: 3, column: 8)");
 
@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+
+/// Whether a comment is documentation: `/// text` or `/** text */`, but not `////`, `/***` or
+/// the empty `/**/`.
+pub fn is_doc_comment(comment: &str) -> bool {
+    if let Some(rest) = comment.strip_prefix("///") {
+        !rest.starts_with('/')
+    } else if let Some(rest) = comment.strip_prefix("/**") {
+        !rest.starts_with(['*', '/'])
+    } else {
+        false
+    }
+}
+
+/// Body of a doc comment without its markers: the `///` or `/**`/`*/` delimiters, the leading `*`
+/// of each block line and the single space that usually follows them.
+pub fn doc_comment_body(comment: &str) -> Cow<'_, str> {
+    if let Some(line) = comment.strip_prefix("///") {
+        let line = line.trim_end_matches('\r');
+        return Cow::Borrowed(line.strip_prefix(' ').unwrap_or(line));
+    }
+    let inner = comment.strip_prefix("/**").unwrap_or(comment);
+    let inner = inner.strip_suffix("*/").unwrap_or(inner);
+    let lines: Vec<&str> = inner
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.trim_end();
+            let line = if index == 0 {
+                line
+            } else {
+                let trimmed = line.trim_start();
+                trimmed.strip_prefix('*').unwrap_or(trimmed)
+            };
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect();
+    let first = lines.iter().position(|line| !line.is_empty());
+    let last = lines.iter().rposition(|line| !line.is_empty());
+    match (first, last) {
+        (Some(first), Some(last)) if first == last => Cow::Borrowed(lines[first]),
+        (Some(first), Some(last)) => Cow::Owned(lines[first..=last].join("\n")),
+        _ => Cow::Borrowed(""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_doc_comment() {
+        assert!(is_doc_comment("/// Adds two numbers."));
+        assert!(is_doc_comment("///"));
+        assert!(is_doc_comment("/** Adds two numbers. */"));
+        assert!(!is_doc_comment("// comment"));
+        assert!(!is_doc_comment("//// separator"));
+        assert!(!is_doc_comment("/* comment */"));
+        assert!(!is_doc_comment("/*** banner ***/"));
+        assert!(!is_doc_comment("/**/"));
+    }
+
+    #[test]
+    fn test_doc_comment_body() {
+        assert_eq!(
+            doc_comment_body("/// Adds two numbers."),
+            "Adds two numbers."
+        );
+        assert_eq!(doc_comment_body("///indented"), "indented");
+        assert_eq!(
+            doc_comment_body("/** Adds two numbers. */"),
+            "Adds two numbers."
+        );
+        assert_eq!(
+            doc_comment_body("/**\n * Adds two numbers.\n *\n *   Returns the sum.\n */"),
+            "Adds two numbers.\n\n  Returns the sum."
+        );
+    }
+}
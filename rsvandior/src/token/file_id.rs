@@ -0,0 +1,79 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+#[derive(Default)]
+struct FileInterner {
+    names: Vec<Arc<str>>,
+    ids: HashMap<Arc<str>, FileId>,
+}
+
+fn interner() -> &'static Mutex<FileInterner> {
+    static INTERNER: OnceLock<Mutex<FileInterner>> = OnceLock::new();
+    INTERNER.get_or_init(|| Mutex::new(FileInterner::default()))
+}
+
+/// Interned file name: tokens and locations carry this copyable id instead of the name itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(u32);
+
+impl FileId {
+    pub fn intern(file_name: &str) -> Self {
+        let mut interner = interner().lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(id) = interner.ids.get(file_name) {
+            return *id;
+        }
+        let id = FileId(interner.names.len() as u32);
+        let name: Arc<str> = Arc::from(file_name);
+        interner.names.push(name.clone());
+        interner.ids.insert(name, id);
+        id
+    }
+
+    pub fn name(&self) -> Arc<str> {
+        let interner = interner().lock().unwrap_or_else(|err| err.into_inner());
+        interner.names[self.0 as usize].clone()
+    }
+
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Serialized as the file name, since ids are only meaningful inside one process.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FileId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FileId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        Ok(FileId::intern(&name))
+    }
+}
+
+impl fmt::Display for FileId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_returns_the_same_id() {
+        let id = FileId::intern("file_id_test.vn");
+        assert_eq!(FileId::intern("file_id_test.vn"), id);
+        assert_ne!(FileId::intern("other_file_id_test.vn"), id);
+        assert_eq!(&*id.name(), "file_id_test.vn");
+        assert_eq!(format!("{}", id), "file_id_test.vn");
+    }
+}
@@ -0,0 +1,195 @@
+use super::span::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatSegmentKind {
+    Text,
+    /// `{{` or `}}`, printed as a single brace.
+    EscapedBrace,
+    /// `{}` when `index` is `None`, `{0}` otherwise.
+    Placeholder {
+        index: Option<usize>,
+    },
+    /// An unmatched brace or a placeholder with unsupported contents.
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatSegment {
+    pub kind: FormatSegmentKind,
+    pub span: Span,
+}
+
+impl FormatSegment {
+    pub fn new(kind: FormatSegmentKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn is_placeholder(&self) -> bool {
+        matches!(self.kind, FormatSegmentKind::Placeholder { .. })
+    }
+}
+
+/// Placeholder structure of a `print`/`println` string literal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatString {
+    pub segments: Vec<FormatSegment>,
+}
+
+impl FormatString {
+    /// Splits the raw body of a string literal; `offset` is where the body starts in the source,
+    /// so that segment spans point into it.
+    pub fn parse(body: &str, offset: usize) -> Self {
+        let mut segments: Vec<FormatSegment> = Vec::new();
+        let mut push = |kind: FormatSegmentKind, start: usize, end: usize| {
+            segments.push(FormatSegment::new(
+                kind,
+                Span::new(offset + start, offset + end),
+            ));
+        };
+        let bytes = body.as_bytes();
+        let mut position = 0;
+        while position < bytes.len() {
+            let start = position;
+            match bytes[position] {
+                b'{' if bytes.get(position + 1) == Some(&b'{') => {
+                    position += 2;
+                    push(FormatSegmentKind::EscapedBrace, start, position);
+                }
+                b'}' if bytes.get(position + 1) == Some(&b'}') => {
+                    position += 2;
+                    push(FormatSegmentKind::EscapedBrace, start, position);
+                }
+                b'{' => match body[start..].find('}') {
+                    Some(close) => {
+                        let contents = &body[start + 1..start + close];
+                        position = start + close + 1;
+                        let kind = if contents.is_empty() {
+                            FormatSegmentKind::Placeholder { index: None }
+                        } else if contents.bytes().all(|byte| byte.is_ascii_digit()) {
+                            contents
+                                .parse()
+                                .map_or(FormatSegmentKind::Invalid, |index| {
+                                    FormatSegmentKind::Placeholder { index: Some(index) }
+                                })
+                        } else {
+                            FormatSegmentKind::Invalid
+                        };
+                        push(kind, start, position);
+                    }
+                    None => {
+                        position = bytes.len();
+                        push(FormatSegmentKind::Invalid, start, position);
+                    }
+                },
+                b'}' => {
+                    position += 1;
+                    push(FormatSegmentKind::Invalid, start, position);
+                }
+                _ => {
+                    position = body[start..]
+                        .find(['{', '}'])
+                        .map_or(bytes.len(), |next| start + next);
+                    push(FormatSegmentKind::Text, start, position);
+                }
+            }
+        }
+        Self { segments }
+    }
+
+    pub fn placeholders(&self) -> impl Iterator<Item = &FormatSegment> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.is_placeholder())
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &FormatSegment> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.kind == FormatSegmentKind::Invalid)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Number of arguments the placeholders refer to: `{}` take the next argument in order,
+    /// `{n}` the `n`-th one.
+    pub fn argument_count(&self) -> usize {
+        let mut implicit = 0;
+        let mut count = 0;
+        for segment in self.placeholders() {
+            if let FormatSegmentKind::Placeholder { index } = segment.kind {
+                let index = index.unwrap_or_else(|| {
+                    implicit += 1;
+                    implicit - 1
+                });
+                count = count.max(index + 1);
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(body: &str) -> Vec<(FormatSegmentKind, Span)> {
+        FormatString::parse(body, 0)
+            .segments
+            .into_iter()
+            .map(|segment| (segment.kind, segment.span))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_placeholders() {
+        use FormatSegmentKind::*;
+        assert_eq!(
+            kinds("Funzione: {} {0}, {{x}}"),
+            vec![
+                (Text, Span::new(0, 10)),
+                (Placeholder { index: None }, Span::new(10, 12)),
+                (Text, Span::new(12, 13)),
+                (Placeholder { index: Some(0) }, Span::new(13, 16)),
+                (Text, Span::new(16, 18)),
+                (EscapedBrace, Span::new(18, 20)),
+                (Text, Span::new(20, 21)),
+                (EscapedBrace, Span::new(21, 23)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_segments() {
+        use FormatSegmentKind::*;
+        assert_eq!(
+            kinds("a } {x} {"),
+            vec![
+                (Text, Span::new(0, 2)),
+                (Invalid, Span::new(2, 3)),
+                (Text, Span::new(3, 4)),
+                (Invalid, Span::new(4, 7)),
+                (Text, Span::new(7, 8)),
+                (Invalid, Span::new(8, 9)),
+            ]
+        );
+        assert!(!FormatString::parse("{", 0).is_valid());
+        assert!(FormatString::parse("{{}}", 0).is_valid());
+    }
+
+    #[test]
+    fn test_spans_are_offset() {
+        let format = FormatString::parse("a {}", 9);
+        let placeholder = format.placeholders().next().unwrap();
+        assert_eq!(placeholder.span, Span::new(11, 13));
+    }
+
+    #[test]
+    fn test_argument_count() {
+        assert_eq!(FormatString::parse("no args", 0).argument_count(), 0);
+        assert_eq!(FormatString::parse("{} {} {}", 0).argument_count(), 3);
+        assert_eq!(FormatString::parse("{1} {}", 0).argument_count(), 2);
+        assert_eq!(FormatString::parse("{0} {0} {{}}", 0).argument_count(), 1);
+    }
+}
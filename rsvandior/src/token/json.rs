@@ -0,0 +1,77 @@
+use serde::Serialize;
+
+use super::span::Span;
+use super::token::Token;
+use super::token_type::TokenType;
+
+/// One entry of `tokens_to_json`, flattened for editor plugins and debugging.
+#[derive(Debug, Serialize)]
+struct TokenDump<'a> {
+    #[serde(rename = "type")]
+    token_type: &'a TokenType,
+    compact: &'static str,
+    value: &'a str,
+    span: Span,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl<'a> From<&'a Token<'_>> for TokenDump<'a> {
+    fn from(token: &'a Token<'_>) -> Self {
+        Self {
+            token_type: &token.token_type,
+            compact: token.token_type.compact_to_string(),
+            value: token.value,
+            span: token.span,
+            line: token.source_location.line,
+            column: token.source_location.column,
+            end_line: token.source_location.end_line,
+            end_column: token.source_location.end_column,
+        }
+    }
+}
+
+/// Dumps a token stream as a pretty-printed JSON array.
+pub fn tokens_to_json(tokens: &[Token<'_>]) -> serde_json::Result<String> {
+    let dump: Vec<TokenDump> = tokens.iter().map(TokenDump::from).collect();
+    serde_json::to_string_pretty(&dump)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Tokenizer;
+
+    #[test]
+    fn test_tokens_to_json() {
+        let (tokens, _) = Tokenizer::new("test.vn", "var a").tokenize();
+        let json: serde_json::Value =
+            serde_json::from_str(&tokens_to_json(&tokens).unwrap()).unwrap();
+        assert_eq!(
+            json[1],
+            serde_json::json!({
+                "type": "Identifier",
+                "compact": "IDENT",
+                "value": "a",
+                "span": { "start": 4, "end": 5 },
+                "line": 1,
+                "column": 4,
+                "end_line": 1,
+                "end_column": 5
+            })
+        );
+        assert_eq!(json.as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_token_serialize() {
+        let (tokens, _) = Tokenizer::new("serde_test.vn", "#ff").tokenize();
+        let json = serde_json::to_value(&tokens[0]).unwrap();
+        assert_eq!(json["token_type"], "Integer");
+        assert_eq!(json["source_location"]["file_id"], "serde_test.vn");
+        assert_eq!(json["numeric"]["radix"], "Hexadecimal");
+        assert_eq!(json["numeric"]["value"]["Integer"], 255);
+    }
+}
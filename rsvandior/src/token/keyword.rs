@@ -0,0 +1,92 @@
+use super::token_type::TokenType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keyword {
+    pub text: &'static str,
+    pub token_type: TokenType,
+    pub compact: &'static str,
+}
+
+impl Keyword {
+    const fn new(text: &'static str, token_type: TokenType, compact: &'static str) -> Self {
+        Self {
+            text,
+            token_type,
+            compact,
+        }
+    }
+
+    pub fn find(text: &str) -> Option<&'static Keyword> {
+        KEYWORDS.iter().find(|keyword| keyword.text == text)
+    }
+
+    pub fn find_by_type(token_type: &TokenType) -> Option<&'static Keyword> {
+        KEYWORDS
+            .iter()
+            .find(|keyword| &keyword.token_type == token_type)
+    }
+
+    pub fn is_reserved(&self) -> bool {
+        self.token_type == TokenType::KReserved
+    }
+}
+
+/// Every word the lexer treats specially, the single source for keyword lookups.
+///
+/// `true` and `false` map to `TokenType::Boolean` like the dedicated boolean rule of the lexer,
+/// and words reserved for future use map to `TokenType::KReserved` so they cannot be taken as
+/// identifiers.
+pub static KEYWORDS: [Keyword; 24] = [
+    Keyword::new("main", TokenType::KMain, "K_MAIN"),
+    Keyword::new("var", TokenType::KVar, "K_VAR"),
+    Keyword::new("const", TokenType::KConst, "K_CONST"),
+    Keyword::new("if", TokenType::KIf, "K_IF"),
+    Keyword::new("while", TokenType::KWhile, "K_WHILE"),
+    Keyword::new("else", TokenType::KElse, "K_ELSE"),
+    Keyword::new("for", TokenType::KFor, "K_FOR"),
+    Keyword::new("break", TokenType::KBreak, "BREAK"),
+    Keyword::new("continue", TokenType::KContinue, "K_CONTINUE"),
+    Keyword::new("fun", TokenType::KFun, "K_FUN"),
+    Keyword::new("return", TokenType::KReturn, "K_RETURN"),
+    Keyword::new("nullptr", TokenType::KNullptr, "K_NULLPTR"),
+    Keyword::new("true", TokenType::Boolean, "BOOL"),
+    Keyword::new("false", TokenType::Boolean, "BOOL"),
+    Keyword::new("class", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("struct", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("enum", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("interface", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("import", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("module", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("match", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("static", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("this", TokenType::KReserved, "K_RESERVED"),
+    Keyword::new("super", TokenType::KReserved, "K_RESERVED"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            Keyword::find("const").unwrap().token_type,
+            TokenType::KConst
+        );
+        assert_eq!(
+            Keyword::find("true").unwrap().token_type,
+            TokenType::Boolean
+        );
+        assert!(Keyword::find("class").unwrap().is_reserved());
+        assert!(Keyword::find("Const").is_none());
+    }
+
+    #[test]
+    fn test_keyword_texts_are_unique() {
+        for (index, keyword) in KEYWORDS.iter().enumerate() {
+            assert!(KEYWORDS[index + 1..]
+                .iter()
+                .all(|other| other.text != keyword.text));
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+
+use super::token::Token;
+use super::trivia::Trivia;
+
+/// A token together with its exact source text and the trivia around it.
+///
+/// Trailing trivia extends up to and including the first newline after the token, everything
+/// else belongs to the leading trivia of the next token, so writing every token of a stream in
+/// order reproduces the original input byte-for-byte.
+#[derive(Debug, Clone)]
+pub struct LosslessToken<'src> {
+    pub token: Token<'src>,
+    pub text: &'src str,
+    pub leading_trivia: Vec<Trivia<'src>>,
+    pub trailing_trivia: Vec<Trivia<'src>>,
+}
+
+impl<'src> LosslessToken<'src> {
+    pub fn new(
+        token: Token<'src>,
+        text: &'src str,
+        leading_trivia: Vec<Trivia<'src>>,
+        trailing_trivia: Vec<Trivia<'src>>,
+    ) -> Self {
+        Self {
+            token,
+            text,
+            leading_trivia,
+            trailing_trivia,
+        }
+    }
+
+    pub fn to_full_string(tokens: &[LosslessToken<'_>]) -> String {
+        tokens.iter().map(|token| token.to_string()).collect()
+    }
+}
+
+impl fmt::Display for LosslessToken<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for trivia in &self.leading_trivia {
+            write!(f, "{}", trivia)?;
+        }
+        write!(f, "{}", self.text)?;
+        for trivia in &self.trailing_trivia {
+            write!(f, "{}", trivia)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,34 @@
+#[allow(clippy::module_inception)]
 pub mod token;
 pub mod token_type;
+pub mod associativity;
 pub mod code_source_location;
+pub mod doc_comment;
+pub mod file_id;
+pub mod format_string;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod keyword;
+pub mod lossless_token;
+pub mod numeric_literal;
+pub mod span;
+pub mod string_literal;
 pub mod token_list;
+pub mod trivia;
 
 pub use token::*;
 pub use token_type::*;
+pub use associativity::*;
 pub use code_source_location::*;
-pub use token_list::*;
\ No newline at end of file
+pub use doc_comment::*;
+pub use file_id::*;
+pub use format_string::*;
+#[cfg(feature = "serde")]
+pub use json::*;
+pub use keyword::*;
+pub use lossless_token::*;
+pub use numeric_literal::*;
+pub use span::*;
+pub use string_literal::*;
+pub use token_list::*;
+pub use trivia::*;
\ No newline at end of file
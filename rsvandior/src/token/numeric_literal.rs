@@ -0,0 +1,241 @@
+use std::fmt;
+
+use crate::lexer::LexErrorKind;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Radix {
+    Binary = 2,
+    Octal = 8,
+    Decimal = 10,
+    Hexadecimal = 16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumericValue {
+    Integer(u64),
+    Float(f64),
+}
+
+/// Value of a number token, decoded once by the lexer.
+///
+/// Decimal literals take the `i` suffix for imaginary numbers and the `f` suffix for `f32`
+/// precision (in this order when both are present); a fractional part, an exponent or the `f`
+/// suffix make the literal a float. `#ff`, `#o17` and `##101` are hexadecimal, octal and binary
+/// integers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumericLiteral {
+    pub value: NumericValue,
+    pub radix: Radix,
+    pub imaginary: bool,
+    pub is_f32: bool,
+}
+
+impl NumericLiteral {
+    pub fn parse(raw: &str) -> Result<Self, LexErrorKind> {
+        if let Some(digits) = raw.strip_prefix("##") {
+            Self::parse_integer(digits, Radix::Binary)
+        } else if let Some(digits) = raw.strip_prefix("#o") {
+            Self::parse_integer(digits, Radix::Octal)
+        } else if let Some(digits) = raw.strip_prefix('#') {
+            Self::parse_integer(digits, Radix::Hexadecimal)
+        } else {
+            Self::parse_decimal(raw)
+        }
+    }
+
+    fn parse_integer(digits: &str, radix: Radix) -> Result<Self, LexErrorKind> {
+        let value = u64::from_str_radix(digits, radix as u32).map_err(|err| match err.kind() {
+            std::num::IntErrorKind::PosOverflow => LexErrorKind::NumberOverflow,
+            _ => LexErrorKind::MalformedNumber,
+        })?;
+        Ok(Self {
+            value: NumericValue::Integer(value),
+            radix,
+            imaginary: false,
+            is_f32: false,
+        })
+    }
+
+    fn parse_decimal(raw: &str) -> Result<Self, LexErrorKind> {
+        let body = raw.trim_end_matches(['i', 'f']);
+        let (imaginary, is_f32) = match &raw[body.len()..] {
+            "" => (false, false),
+            "i" => (true, false),
+            "f" => (false, true),
+            "if" => (true, true),
+            _ => return Err(LexErrorKind::InvalidNumberSuffix),
+        };
+        let value = if is_f32 || body.contains(['.', 'e', 'E']) {
+            let value: f64 = body.parse().map_err(|_| LexErrorKind::MalformedNumber)?;
+            if value.is_infinite() || (is_f32 && value.abs() > f32::MAX as f64) {
+                return Err(LexErrorKind::NumberOverflow);
+            }
+            NumericValue::Float(value)
+        } else {
+            NumericValue::Integer(body.parse().map_err(|err: std::num::ParseIntError| {
+                match err.kind() {
+                    std::num::IntErrorKind::PosOverflow => LexErrorKind::NumberOverflow,
+                    _ => LexErrorKind::MalformedNumber,
+                }
+            })?)
+        };
+        Ok(Self {
+            value,
+            radix: Radix::Decimal,
+            imaginary,
+            is_f32,
+        })
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self.value, NumericValue::Float(_))
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.value {
+            NumericValue::Integer(value) => Some(value),
+            NumericValue::Float(_) => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self.value {
+            NumericValue::Integer(value) => value as f64,
+            NumericValue::Float(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for NumericLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            NumericValue::Integer(value) => write!(f, "{}", value)?,
+            NumericValue::Float(value) => write!(f, "{:?}", value)?,
+        }
+        if self.imaginary {
+            write!(f, "i")?;
+        }
+        if self.is_f32 {
+            write!(f, "f")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(value: NumericValue, radix: Radix, imaginary: bool, is_f32: bool) -> NumericLiteral {
+        NumericLiteral {
+            value,
+            radix,
+            imaginary,
+            is_f32,
+        }
+    }
+
+    #[test]
+    fn test_parse_integers() {
+        use NumericValue::Integer;
+        assert_eq!(
+            NumericLiteral::parse("334"),
+            Ok(literal(Integer(334), Radix::Decimal, false, false))
+        );
+        assert_eq!(
+            NumericLiteral::parse("#o3444"),
+            Ok(literal(Integer(0o3444), Radix::Octal, false, false))
+        );
+        assert_eq!(
+            NumericLiteral::parse("##1010"),
+            Ok(literal(Integer(10), Radix::Binary, false, false))
+        );
+        assert_eq!(
+            NumericLiteral::parse("#334affff"),
+            Ok(literal(
+                Integer(0x334affff),
+                Radix::Hexadecimal,
+                false,
+                false
+            ))
+        );
+        assert_eq!(
+            NumericLiteral::parse("4i"),
+            Ok(literal(Integer(4), Radix::Decimal, true, false))
+        );
+    }
+
+    #[test]
+    fn test_parse_floats() {
+        use NumericValue::Float;
+        assert_eq!(
+            NumericLiteral::parse("1.222222222222f"),
+            Ok(literal(Float(1.222222222222), Radix::Decimal, false, true))
+        );
+        assert_eq!(
+            NumericLiteral::parse("34.5i"),
+            Ok(literal(Float(34.5), Radix::Decimal, true, false))
+        );
+        assert_eq!(
+            NumericLiteral::parse("2.3if"),
+            Ok(literal(Float(2.3), Radix::Decimal, true, true))
+        );
+        assert_eq!(
+            NumericLiteral::parse("4if"),
+            Ok(literal(Float(4.0), Radix::Decimal, true, true))
+        );
+        assert_eq!(
+            NumericLiteral::parse("1e3"),
+            Ok(literal(Float(1000.0), Radix::Decimal, false, false))
+        );
+        assert_eq!(
+            NumericLiteral::parse(".5"),
+            Ok(literal(Float(0.5), Radix::Decimal, false, false))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            NumericLiteral::parse("18446744073709551616"),
+            Err(LexErrorKind::NumberOverflow)
+        );
+        assert_eq!(
+            NumericLiteral::parse("#1ffffffffffffffff"),
+            Err(LexErrorKind::NumberOverflow)
+        );
+        assert_eq!(
+            NumericLiteral::parse("1e999"),
+            Err(LexErrorKind::NumberOverflow)
+        );
+        assert_eq!(
+            NumericLiteral::parse("1e39f"),
+            Err(LexErrorKind::NumberOverflow)
+        );
+        assert_eq!(
+            NumericLiteral::parse("1ff"),
+            Err(LexErrorKind::InvalidNumberSuffix)
+        );
+        assert_eq!(
+            NumericLiteral::parse("1fi"),
+            Err(LexErrorKind::InvalidNumberSuffix)
+        );
+    }
+
+    #[test]
+    fn test_accessors_and_display() {
+        let integer = NumericLiteral::parse("#ff").unwrap();
+        assert_eq!(integer.as_u64(), Some(255));
+        assert_eq!(integer.as_f64(), 255.0);
+        assert!(!integer.is_float());
+        let float = NumericLiteral::parse("2.5if").unwrap();
+        assert_eq!(float.as_u64(), None);
+        assert!(float.is_float());
+        assert_eq!(format!("{}", float), "2.5if");
+        assert_eq!(format!("{}", integer), "255");
+    }
+}
@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+
+use super::span::Span;
+use crate::lexer::LexErrorKind;
+
+/// An escape sequence that could not be decoded, with its byte range inside the literal body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapeError {
+    pub kind: LexErrorKind,
+    pub span: Span,
+}
+
+impl EscapeError {
+    pub fn new(kind: LexErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// Decodes the escape sequences of a string or char literal body, without its quotes.
+///
+/// Accepts `\n`, `\t`, `\r`, `\\`, `\"`, `\'`, `\0`, `\xHH` (up to `\x7F`) and `\u{H..}` (one
+/// to six hex digits). Bodies without escapes are returned borrowed; otherwise every bad escape
+/// is reported.
+pub fn unescape(body: &str) -> Result<Cow<'_, str>, Vec<EscapeError>> {
+    if !body.contains('\\') {
+        return Ok(Cow::Borrowed(body));
+    }
+    let mut cooked = String::with_capacity(body.len());
+    let mut errors: Vec<EscapeError> = Vec::new();
+    let mut chars = body.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            cooked.push(c);
+            continue;
+        }
+        let decoded = match chars.next() {
+            Some((_, 'n')) => Some('\n'),
+            Some((_, 't')) => Some('\t'),
+            Some((_, 'r')) => Some('\r'),
+            Some((_, '\\')) => Some('\\'),
+            Some((_, '"')) => Some('"'),
+            Some((_, '\'')) => Some('\''),
+            Some((_, '0')) => Some('\0'),
+            Some((_, 'x')) => {
+                let mut digits = String::new();
+                while digits.len() < 2 {
+                    match chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                        Some((_, digit)) => digits.push(digit),
+                        None => break,
+                    }
+                }
+                u8::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|value| digits.len() == 2 && value.is_ascii())
+                    .map(char::from)
+            }
+            Some((_, 'u')) if chars.next_if(|(_, c)| *c == '{').is_some() => {
+                let mut digits = String::new();
+                while let Some((_, digit)) = chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                    digits.push(digit);
+                }
+                let closed = chars.next_if(|(_, c)| *c == '}').is_some();
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|_| closed && digits.len() <= 6)
+                    .and_then(char::from_u32)
+            }
+            _ => None,
+        };
+        match decoded {
+            Some(decoded) => cooked.push(decoded),
+            None => {
+                let end = chars.peek().map_or(body.len(), |(offset, _)| *offset);
+                errors.push(EscapeError::new(
+                    LexErrorKind::BadEscape,
+                    Span::new(start, end),
+                ));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(Cow::Owned(cooked))
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unescape_without_escapes_borrows() {
+        assert!(matches!(
+            unescape("Funzione: {}"),
+            Ok(Cow::Borrowed("Funzione: {}"))
+        ));
+    }
+
+    #[test]
+    fn test_unescape_simple_escapes() {
+        assert_eq!(
+            unescape(r#"Funzione:\t{}\n\\ \" \' \0"#).unwrap(),
+            "Funzione:\t{}\n\\ \" ' \0"
+        );
+    }
+
+    #[test]
+    fn test_unescape_hex_and_unicode() {
+        assert_eq!(unescape(r"\x41\x7f").unwrap(), "A\x7f");
+        assert_eq!(unescape(r"\u{48}\u{e8}\u{1F600}").unwrap(), "Hè😀");
+    }
+
+    #[test]
+    fn test_unescape_reports_every_bad_escape() {
+        let errors = unescape(r"a\qb\x4g\xff\u{110000}\u{41\u41").unwrap_err();
+        let spans: Vec<Span> = errors.iter().map(|error| error.span).collect();
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(1, 3),
+                Span::new(4, 7),
+                Span::new(8, 12),
+                Span::new(12, 22),
+                Span::new(22, 27),
+                Span::new(27, 29),
+            ]
+        );
+        assert!(errors
+            .iter()
+            .all(|error| error.kind == LexErrorKind::BadEscape));
+    }
+}
@@ -1,40 +1,83 @@
 use super::code_source_location::CodeSourceLocation;
+use super::format_string::FormatString;
+use super::numeric_literal::NumericLiteral;
+use super::span::Span;
 use super::token_type::TokenType;
-use std::ffi::{CStr, CString};
+use std::borrow::Cow;
 use std::fmt;
 
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct Token {
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Token<'src> {
     pub token_type: TokenType,
-    pub value: *const std::os::raw::c_char,
+    pub value: &'src str,
+    pub span: Span,
     pub source_location: CodeSourceLocation,
+    /// Decoded value of `Integer` and `Double` tokens produced by the tokenizer.
+    pub numeric: Option<NumericLiteral>,
+    /// `String` and `Char` value with its escape sequences decoded, or `DocComment` body without
+    /// its markers; `value` keeps the raw text.
+    pub cooked: Option<Cow<'src, str>>,
 }
 #[allow(dead_code)]
-impl Token {
-    pub fn new(token_type: TokenType, value: String, source_location: CodeSourceLocation) -> Self {
+impl<'src> Token<'src> {
+    pub fn new(
+        token_type: TokenType,
+        value: &'src str,
+        source_location: CodeSourceLocation,
+    ) -> Self {
+        Self::new_with_span(token_type, value, Span::default(), source_location)
+    }
+
+    pub fn new_with_span(
+        token_type: TokenType,
+        value: &'src str,
+        span: Span,
+        source_location: CodeSourceLocation,
+    ) -> Self {
         Self {
             token_type,
-            value: CString::new(value)
-                .unwrap_or_else(|err| panic!("Tokenization failed: {}", err))
-                .into_raw(),
+            value,
+            span,
             source_location,
+            numeric: None,
+            cooked: None,
         }
     }
 
-    pub fn new_with_empty_value(
+    pub fn new_with_numeric(
         token_type: TokenType,
+        value: &'src str,
+        span: Span,
         source_location: CodeSourceLocation,
+        numeric: NumericLiteral,
     ) -> Self {
         Self {
-            token_type,
-            value: CString::new("")
-                .unwrap_or_else(|err| panic!("Tokenization failed: {}", err))
-                .into_raw(),
-            source_location,
+            numeric: Some(numeric),
+            ..Self::new_with_span(token_type, value, span, source_location)
+        }
+    }
+
+    pub fn new_with_cooked(
+        token_type: TokenType,
+        value: &'src str,
+        span: Span,
+        source_location: CodeSourceLocation,
+        cooked: Cow<'src, str>,
+    ) -> Self {
+        Self {
+            cooked: Some(cooked),
+            ..Self::new_with_span(token_type, value, span, source_location)
         }
     }
 
+    pub fn new_with_empty_value(
+        token_type: TokenType,
+        source_location: CodeSourceLocation,
+    ) -> Self {
+        Self::new(token_type, "", source_location)
+    }
+
     pub fn is_type(&self, token_type: &TokenType) -> bool {
         &self.token_type == token_type
     }
@@ -43,16 +86,18 @@ impl Token {
         token_types.contains(&self.token_type)
     }
 
+    /// Placeholders of a `String` token, with spans into the source; `None` for other tokens.
+    pub fn format_string(&self) -> Option<FormatString> {
+        self.is_type(&TokenType::String)
+            .then(|| FormatString::parse(self.value, self.span.start + 1))
+    }
+
     pub fn value_size(&self) -> usize {
-        let c_str1 = unsafe { CStr::from_ptr(self.value) };
-        let value_str = c_str1.to_str().unwrap_or("Unknown");
-        value_str.len()
+        self.value.len()
     }
 
     pub fn to_compact_string(&self) -> String {
-        let c_str1 = unsafe { CStr::from_ptr(self.value) };
-        let value_str = c_str1.to_str().unwrap_or("Unknown");
-        if value_str.is_empty() {
+        if self.value.is_empty() {
             format!(
                 "(typ: {:#}, sl: {:#})",
                 self.token_type, self.source_location
@@ -60,33 +105,29 @@ impl Token {
         } else {
             format!(
                 "(typ: {:#}, val: '{}', sl: {:#})",
-                self.token_type, value_str, self.source_location
+                self.token_type, self.value, self.source_location
             )
         }
     }
 }
 
-impl fmt::Display for Token {
+impl fmt::Display for Token<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if f.alternate() {
             // Usa il formato compatto con "{:#}".
             write!(f, "{}", self.to_compact_string())
+        } else if self.value.is_empty() {
+            write!(
+                f,
+                "Token(type: {:?}, sourceLocation: {})",
+                self.token_type, self.source_location
+            )
         } else {
-            let c_str1 = unsafe { CStr::from_ptr(self.value) };
-            let value_str = c_str1.to_str().unwrap_or("Unknown");
-            if value_str.is_empty() {
-                write!(
-                    f,
-                    "Token(type: {:?}, sourceLocation: {})",
-                    self.token_type, self.source_location
-                )
-            } else {
-                write!(
-                    f,
-                    "Token(type: {:?}, value: '{}', sourceLocation: {})",
-                    self.token_type, value_str, self.source_location
-                )
-            }
+            write!(
+                f,
+                "Token(type: {:?}, value: '{}', sourceLocation: {})",
+                self.token_type, self.value, self.source_location
+            )
         }
     }
 }
@@ -95,70 +136,101 @@ impl fmt::Display for Token {
 mod tests {
     use super::*;
     use crate::token::code_source_location::CodeSourceLocation;
+    use crate::token::file_id::FileId;
     use crate::token::token_type::TokenType;
 
+    fn location() -> CodeSourceLocation {
+        CodeSourceLocation::new(FileId::intern("test"), 1, 1)
+    }
+
     #[test]
     fn test_token_new() {
-        let token = Token::new(
-            TokenType::Identifier,
-            "test".to_string(),
-            CodeSourceLocation::new("test".to_string(), 1, 1),
-        );
+        let token = Token::new(TokenType::Identifier, "test", location());
         assert_eq!(token.token_type, TokenType::Identifier);
-        let c_str1 = unsafe { CStr::from_ptr(token.value) }
-            .to_str()
-            .unwrap_or("Unknown");
-        assert_eq!(c_str1, "test");
-        assert_eq!(
-            unsafe { CStr::from_ptr(token.source_location.file_name) }
-                .to_str()
-                .unwrap_or("Unknown"),
-            "test"
-        );
+        assert_eq!(token.value, "test");
+        assert_eq!(&*token.source_location.file_name(), "test");
         assert_eq!(token.source_location.line, 1);
         assert_eq!(token.source_location.column, 1);
     }
 
     #[test]
     fn test_token_new_with_empty_value() {
-        let token = Token::new_with_empty_value(
-            TokenType::Identifier,
-            CodeSourceLocation::new("test".to_string(), 1, 1),
-        );
+        let token = Token::new_with_empty_value(TokenType::Identifier, location());
         assert_eq!(token.token_type, TokenType::Identifier);
-        let c_str1 = unsafe { CStr::from_ptr(token.value) }
-            .to_str()
-            .unwrap_or("Unknown");
-        assert_eq!(c_str1, "");
-        assert_eq!(
-            unsafe { CStr::from_ptr(token.source_location.file_name) }
-                .to_str()
-                .unwrap_or("Unknown"),
-            "test"
-        );
+        assert_eq!(token.value, "");
+        assert_eq!(&*token.source_location.file_name(), "test");
         assert_eq!(token.source_location.line, 1);
         assert_eq!(token.source_location.column, 1);
     }
 
     #[test]
-    fn test_token_is_type() {
-        let token = Token::new(
+    fn test_token_new_with_span() {
+        let token = Token::new_with_span(
             TokenType::Identifier,
-            "test".to_string(),
-            CodeSourceLocation::new("test".to_string(), 1, 1),
+            "test",
+            Span::new(4, 8),
+            CodeSourceLocation::new_with_end(FileId::intern("test"), 1, 4, 1, 8),
+        );
+        assert_eq!(token.span, Span::new(4, 8));
+        assert_eq!(token.value_size(), token.span.len());
+        assert_eq!(token.source_location.end_column, 8);
+        assert_eq!(token.numeric, None);
+    }
+
+    #[test]
+    fn test_token_new_with_numeric() {
+        let numeric = NumericLiteral::parse("#ff").unwrap();
+        let token = Token::new_with_numeric(
+            TokenType::Integer,
+            "#ff",
+            Span::new(0, 3),
+            location(),
+            numeric,
+        );
+        assert_eq!(
+            token.numeric.and_then(|numeric| numeric.as_u64()),
+            Some(255)
+        );
+    }
+
+    #[test]
+    fn test_token_format_string() {
+        let token = Token::new_with_span(TokenType::String, "x = {}", Span::new(8, 16), location());
+        let format = token.format_string().unwrap();
+        assert_eq!(format.argument_count(), 1);
+        assert_eq!(
+            format.placeholders().next().unwrap().span,
+            Span::new(13, 15)
         );
+        assert!(Token::new(TokenType::Identifier, "x", location())
+            .format_string()
+            .is_none());
+    }
+
+    #[test]
+    fn test_token_is_type() {
+        let token = Token::new(TokenType::Identifier, "test", location());
         assert!(token.is_type(&TokenType::Identifier));
         assert!(!token.is_type(&TokenType::Integer));
     }
 
     #[test]
     fn test_token_is_type_any_of() {
-        let token = Token::new(
-            TokenType::Identifier,
-            "test".to_string(),
-            CodeSourceLocation::new("test".to_string(), 1, 1),
-        );
+        let token = Token::new(TokenType::Identifier, "test", location());
         assert!(token.is_type_any_of(&[TokenType::Identifier, TokenType::Integer]));
         assert!(!token.is_type_any_of(&[TokenType::Integer]));
     }
+
+    #[test]
+    fn test_display() {
+        let token = Token::new(TokenType::Identifier, "test", location());
+        assert_eq!(
+            format!("{}", token),
+            "Token(type: Identifier, value: 'test', sourceLocation: (file: test,line: 1, column: 1))"
+        );
+        assert_eq!(
+            format!("{:#}", token),
+            "(typ: IDENT, val: 'test', sl: (fn: test, ln: 1, cln: 1))"
+        );
+    }
 }
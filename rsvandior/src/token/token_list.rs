@@ -1,17 +1,16 @@
-use super::Token;
+use super::{FileId, Token};
 
-#[repr(C)]
 #[derive(Debug, Clone)]
-pub struct TokenList {
-    pub file_name: String,
-    pub tokens: Vec<Token>,
+pub struct TokenList<'src> {
+    pub file_id: FileId,
+    pub tokens: Vec<Token<'src>>,
 }
 
-impl TokenList {
-    pub fn new(file_name: String) -> Self {
+impl TokenList<'_> {
+    pub fn new(file_id: FileId) -> Self {
         Self {
-            file_name,
+            file_id,
             tokens: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,65 @@
 use std::fmt;
 
-use logos::Logos;
+use logos::{Lexer, Logos};
+
+use super::associativity::Associativity;
+use super::doc_comment::is_doc_comment;
+use super::keyword::Keyword;
+use crate::lexer::LexErrorKind;
+
+fn unterminated_string(_: &mut Lexer<RawToken>) -> Result<(), LexErrorKind> {
+    Err(LexErrorKind::UnterminatedString)
+}
+
+fn unterminated_char(_: &mut Lexer<RawToken>) -> Result<(), LexErrorKind> {
+    Err(LexErrorKind::UnterminatedChar)
+}
+
+/// Scans a block comment starting with `/*`, counting nested `/* */` pairs. Returns the length of
+/// the comment, or the offset of the innermost opener left unclosed at the end of the input.
+pub fn scan_block_comment(text: &str) -> Result<usize, usize> {
+    let bytes = text.as_bytes();
+    let mut openers: Vec<usize> = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        match (bytes[position], bytes.get(position + 1)) {
+            (b'/', Some(b'*')) => {
+                openers.push(position);
+                position += 2;
+            }
+            (b'*', Some(b'/')) if !openers.is_empty() => {
+                openers.pop();
+                position += 2;
+                if openers.is_empty() {
+                    return Ok(position);
+                }
+            }
+            _ => position += 1,
+        }
+    }
+    Err(openers.pop().unwrap_or_default())
+}
+
+fn block_comment(lex: &mut Lexer<RawToken>) -> Result<(), LexErrorKind> {
+    let start = lex.span().start;
+    match scan_block_comment(&lex.source()[start..]) {
+        Ok(len) => {
+            lex.bump(len - lex.slice().len());
+            Ok(())
+        }
+        Err(_) => {
+            lex.bump(lex.remainder().len());
+            Err(LexErrorKind::UnterminatedBlockComment)
+        }
+    }
+}
+
+fn malformed_number(_: &mut Lexer<RawToken>) -> Result<(), LexErrorKind> {
+    Err(LexErrorKind::MalformedNumber)
+}
 
 #[derive(Logos, Debug, PartialEq, Clone)]
+#[logos(error = LexErrorKind)]
 pub enum RawToken {
     // ASCII identifiers (including underscores)
     #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*", priority = 2)]
@@ -37,7 +94,7 @@ pub enum RawToken {
 
     #[regex(r"//[^\n]*")] // Skip inline comments
     SingleLineComment,
-    #[regex(r"/\*[^*]*\*+(?:[^/*][^*]*\*+)*/")] // Skip multi-line comments
+    #[token("/*", block_comment)] // Skip multi-line comments, which can be nested
     MultiLineComment,
 
     // Operators
@@ -75,8 +132,14 @@ pub enum RawToken {
     MinusMinus,
     #[token("+=")]
     PlusEqual,
-    #[token("-?")]
+    #[token("-=")]
     MinusEqual,
+    #[token("*=")]
+    StarEqual,
+    #[token("/=")]
+    SlashEqual,
+    #[token("==")]
+    EqualEqual,
     #[token("<=")]
     LessEqual,
     #[token(">=")]
@@ -87,6 +150,16 @@ pub enum RawToken {
     XorEqual,
     #[token("%=")]
     PercentEqual,
+    #[token("|=")]
+    OrEqual,
+    #[token("&=")]
+    AndEqual,
+    #[token("<<")]
+    ShiftLeft,
+    #[token(">>")]
+    ShiftRight,
+    #[token("->")]
+    Arrow,
 
     /*#[token("%")]
     PERCENT,*/
@@ -116,14 +189,25 @@ pub enum RawToken {
     #[regex(r"true|false", priority = 5)]
     BOOLEAN,
 
-    // Strings: Matches double-quoted strings, including escape sequences
+    // Strings: Matches double-quoted strings, including escape sequences (decoded by the tokenizer)
     #[regex(r#""([^"\\]|\\.)*""#)]
     STRING,
 
     // Characters: Matches single-quoted characters, including escape sequences
-    #[regex(r#"'([^'\\]|\\.)'"#)]
+    #[regex(r#"'([^'\\]|\\x[0-9a-fA-F]{0,2}|\\u\{[0-9a-fA-F]*\}?|\\.)'"#)]
     CHAR,
 
+    // Malformed input that still has a recognizable shape, reported as a lexical error
+    #[regex(r#""([^"\\]|\\.)*\\?"#, unterminated_string)]
+    #[regex(r#"'([^'\\]|\\.)?\\?"#, unterminated_char)]
+    #[regex(r"#{1,2}[0-9a-zA-Z_]*", malformed_number, priority = 1)]
+    #[regex(
+        r"(\d*\.\d+|\d+\.|\d+)([eE][+-]?\d+)?[if]*[a-zA-Z_][a-zA-Z0-9_]*",
+        malformed_number,
+        priority = 3
+    )]
+    Invalid,
+
     // Single dot
     #[token(".")]
     Dot,
@@ -164,6 +248,7 @@ pub enum RawToken {
 #[repr(C)]
 #[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TokenType {
     Integer,
     Double,
@@ -194,6 +279,11 @@ pub enum TokenType {
     EqualEqual,
     LessEqual,
     GreaterEqual,
+    OrEqual,
+    AndEqual,
+    ShiftLeft,
+    ShiftRight,
+    Arrow,
     Dot,
     Identifier,
     Char,
@@ -208,6 +298,9 @@ pub enum TokenType {
     KFun,
     KReturn,
     KNullptr,
+    KConst,
+    KContinue,
+    KReserved,
     OpenParenthesis,
     OpenSqParenthesis,
     OpenCurParenthesis,
@@ -232,160 +325,240 @@ pub enum TokenType {
     TypeString,
     TypeBool,
     Comment,
+    DocComment,
     Unknown,
     Eoft,
 }
 
 #[allow(dead_code)]
 impl TokenType {
+    pub const OPERATORS: [TokenType; 34] = [
+        TokenType::Plus,
+        TokenType::Minus,
+        TokenType::Not,
+        TokenType::Star,
+        TokenType::Divide,
+        TokenType::Xor,
+        TokenType::Percent,
+        TokenType::Or,
+        TokenType::And,
+        TokenType::Equal,
+        TokenType::Less,
+        TokenType::Greater,
+        TokenType::PlusPlus,
+        TokenType::MinusMinus,
+        TokenType::PlusEqual,
+        TokenType::MinusEqual,
+        TokenType::NotEqual,
+        TokenType::StarEqual,
+        TokenType::DivideEqual,
+        TokenType::XorEqual,
+        TokenType::PercentEqual,
+        TokenType::OrOr,
+        TokenType::AndAnd,
+        TokenType::EqualEqual,
+        TokenType::LessEqual,
+        TokenType::GreaterEqual,
+        TokenType::OrEqual,
+        TokenType::AndEqual,
+        TokenType::ShiftLeft,
+        TokenType::ShiftRight,
+        TokenType::Arrow,
+        TokenType::Dot,
+        TokenType::Comma,
+        TokenType::Colon,
+    ];
+
+    pub fn is_operator(&self) -> bool {
+        Self::OPERATORS.contains(self)
+    }
+
     pub fn is_keyword(&self) -> bool {
+        *self != TokenType::Boolean && Keyword::find_by_type(self).is_some()
+    }
+
+    /// Precedence of a binary operator, from the C++ `Parser::operatorPrecedence` table: higher
+    /// binds tighter and `None` means the token is not a binary operator. `,` and `:` keep their
+    /// levels from the table even though the Rust parser treats them as separators.
+    ///
+    /// The operators the C++ table lacks join an existing level, grouped like in Go: `|` with
+    /// the additive operators and `&`, `<<`, `>>` with the multiplicative ones. Every token of
+    /// `OPERATORS` has a level except the prefix-only `!`, `++`, `--` and the `->` separator.
+    pub fn binary_precedence(&self) -> Option<u8> {
+        use TokenType::*;
+        let precedence = match self {
+            Comma => 1,
+            Colon => 2,
+            Equal | PlusEqual | MinusEqual | StarEqual | DivideEqual | XorEqual | PercentEqual
+            | OrEqual | AndEqual => 3,
+            OrOr => 4,
+            AndAnd => 5,
+            EqualEqual | NotEqual => 6,
+            Less | LessEqual | Greater | GreaterEqual => 7,
+            Plus | Minus | Or => 8,
+            Star | Divide | And | ShiftLeft | ShiftRight => 9,
+            Xor | Percent => 10,
+            Dot => 11,
+            _ => return None,
+        };
+        Some(precedence)
+    }
+
+    /// Prefix operators bind tighter than every binary operator, like in
+    /// `Parser::getUnaryOperatorPrecedence`.
+    pub fn unary_precedence(&self) -> Option<u8> {
+        use TokenType::*;
+        matches!(self, Plus | Minus | Not | PlusPlus | MinusMinus).then_some(12)
+    }
+
+    pub fn associativity(&self) -> Associativity {
+        if self.is_assignment_operator() {
+            Associativity::Right
+        } else {
+            Associativity::Left
+        }
+    }
+
+    pub fn is_assignment_operator(&self) -> bool {
+        use TokenType::*;
         matches!(
             self,
-            TokenType::KMain
-                | TokenType::KVar
-                | TokenType::KIf
-                | TokenType::KWhile
-                | TokenType::KElse
-                | TokenType::KFor
-                | TokenType::KBreak
-                | TokenType::KFun
-                | TokenType::KReturn
+            Equal
+                | PlusEqual
+                | MinusEqual
+                | StarEqual
+                | DivideEqual
+                | XorEqual
+                | PercentEqual
+                | OrEqual
+                | AndEqual
         )
     }
 
-    pub fn map_keword_to_token_type(keyword: String) -> TokenType {
-        match keyword.as_str() {
-            "main" => TokenType::KMain,
-            "var" => TokenType::KVar,
-            "if" => TokenType::KIf,
-            "while" => TokenType::KWhile,
-            "else" => TokenType::KElse,
-            "for" => TokenType::KFor,
-            "break" => TokenType::KBreak,
-            "fun" => TokenType::KFun,
-            "return" => TokenType::KReturn,
-            _ => TokenType::Identifier,
-        }
+    /// Operators that combine two expressions into one; unlike `binary_precedence` this leaves
+    /// out the `,` and `:` separators.
+    pub fn is_binary_operator(&self) -> bool {
+        !matches!(self, TokenType::Comma | TokenType::Colon) && self.binary_precedence().is_some()
+    }
+
+    pub fn is_literal(&self) -> bool {
+        use TokenType::*;
+        matches!(self, Integer | Double | Boolean | Char | String)
+    }
+
+    /// Primitive type names, `TypeI8..=TypeBool`.
+    pub fn is_type_keyword(&self) -> bool {
+        use TokenType::*;
+        matches!(
+            self,
+            TypeI8
+                | TypeI16
+                | TypeI32
+                | TypeI64
+                | TypeU8
+                | TypeU16
+                | TypeU32
+                | TypeU64
+                | TypeF32
+                | TypeF64
+                | TypeC32
+                | TypeC64
+                | TypeChar
+                | TypeString
+                | TypeBool
+        )
+    }
+
+    pub fn map_keword_to_token_type(keyword: &str) -> TokenType {
+        Keyword::find(keyword).map_or(TokenType::Identifier, |keyword| keyword.token_type.clone())
     }
 
     pub fn trim_start_and_end(raw: String) -> String {
         raw[1..raw.len() - 1].to_string()
     }
 
-    pub fn map_raw_token_type(raw_token: RawToken, raw_val: String) -> (TokenType, &'static str) {
+    pub fn map_raw_token_type(raw_token: RawToken, raw_val: &str) -> (TokenType, &str) {
         match raw_token {
-            RawToken::IdentifierAscii => (
-                TokenType::map_keword_to_token_type(raw_val.clone()),
-                Box::leak(raw_val.into_boxed_str()),
-            ),
-            RawToken::IdentifierUnicode => {
-                (TokenType::Identifier, Box::leak(raw_val.into_boxed_str()))
-            }
-            RawToken::Binary => (TokenType::Integer, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Hexadecimal => (TokenType::Integer, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Octal => (TokenType::Integer, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Whitespace => (TokenType::Unknown, Box::leak(raw_val.into_boxed_str())),
-            RawToken::SingleLineComment => {
-                (TokenType::Comment, Box::leak(raw_val.into_boxed_str()))
-            }
-            RawToken::MultiLineComment => (TokenType::Comment, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Plus => (TokenType::Plus, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Minus => (TokenType::Minus, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Star => (TokenType::Star, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Slash => (TokenType::Divide, Box::leak(raw_val.into_boxed_str())),
-            RawToken::LESS => (TokenType::Less, Box::leak(raw_val.into_boxed_str())),
-            RawToken::GREATER => (TokenType::Greater, Box::leak(raw_val.into_boxed_str())),
-            RawToken::NOT => (TokenType::Not, Box::leak(raw_val.into_boxed_str())),
-            RawToken::XOR => (TokenType::Xor, Box::leak(raw_val.into_boxed_str())),
-            RawToken::PERCENT => (TokenType::Percent, Box::leak(raw_val.into_boxed_str())),
-            RawToken::OR => (TokenType::Or, Box::leak(raw_val.into_boxed_str())),
-            RawToken::AND => (TokenType::And, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Equal => (TokenType::Equal, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Colon => (TokenType::Colon, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Comma => (TokenType::Comma, Box::leak(raw_val.into_boxed_str())),
-            RawToken::PlusPlus => (TokenType::PlusPlus, Box::leak(raw_val.into_boxed_str())),
-            RawToken::MinusMinus => (TokenType::MinusMinus, Box::leak(raw_val.into_boxed_str())),
-            RawToken::PlusEqual => (TokenType::PlusEqual, Box::leak(raw_val.into_boxed_str())),
-            RawToken::MinusEqual => (TokenType::MinusEqual, Box::leak(raw_val.into_boxed_str())),
-            RawToken::LessEqual => (TokenType::LessEqual, Box::leak(raw_val.into_boxed_str())),
-            RawToken::Greaterequal => {
-                (TokenType::GreaterEqual, Box::leak(raw_val.into_boxed_str()))
-            }
-            RawToken::NotEqual => (TokenType::NotEqual, Box::leak(raw_val.into_boxed_str())),
-            RawToken::XorEqual => (TokenType::XorEqual, Box::leak(raw_val.into_boxed_str())),
-            RawToken::PercentEqual => {
-                (TokenType::PercentEqual, Box::leak(raw_val.into_boxed_str()))
+            RawToken::IdentifierAscii => (TokenType::map_keword_to_token_type(raw_val), raw_val),
+            RawToken::IdentifierUnicode => (TokenType::Identifier, raw_val),
+            RawToken::Binary => (TokenType::Integer, raw_val),
+            RawToken::Hexadecimal => (TokenType::Integer, raw_val),
+            RawToken::Octal => (TokenType::Integer, raw_val),
+            RawToken::Whitespace => (TokenType::Unknown, raw_val),
+            RawToken::SingleLineComment | RawToken::MultiLineComment => {
+                if is_doc_comment(raw_val) {
+                    (TokenType::DocComment, raw_val)
+                } else {
+                    (TokenType::Comment, raw_val)
+                }
             }
-            RawToken::OrOr => (TokenType::OrOr, Box::leak(raw_val.into_boxed_str())),
-            RawToken::AndAnd => (TokenType::AndAnd, Box::leak(raw_val.into_boxed_str())),
-            RawToken::OpenParentesis => (
-                TokenType::OpenParenthesis,
-                Box::leak(raw_val.into_boxed_str()),
-            ),
-            RawToken::CloseParentesis => (
-                TokenType::CloseParenthesis,
-                Box::leak(raw_val.into_boxed_str()),
-            ),
-            RawToken::OpenSQParentesis => (
-                TokenType::OpenSqParenthesis,
-                Box::leak(raw_val.into_boxed_str()),
-            ),
-            RawToken::CloseSQParentesis => (
-                TokenType::CloseSqParenthesis,
-                Box::leak(raw_val.into_boxed_str()),
-            ),
-            RawToken::OpenCurParentesis => (
-                TokenType::OpenCurParenthesis,
-                Box::leak(raw_val.into_boxed_str()),
-            ),
-            RawToken::CloseCurParentesis => (
-                TokenType::CloseCurParenthesis,
-                Box::leak(raw_val.into_boxed_str()),
-            ),
-            RawToken::BOOLEAN => (TokenType::Boolean, Box::leak(raw_val.into_boxed_str())),
-            RawToken::STRING => (
-                TokenType::String,
-                Box::leak(
-                    raw_val
-                        .trim_start_matches('\"')
-                        .trim_end_matches('\"')
-                        .to_string()
-                        .into_boxed_str(),
-                ),
-            ),
-            RawToken::CHAR => (
-                TokenType::Char,
-                Box::leak(
-                    raw_val
-                        .trim_start_matches('\'')
-                        .trim_end_matches('\'')
-                        .to_string()
-                        .into_boxed_str(),
-                ),
-            ),
-            RawToken::Dot => (TokenType::Dot, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEI8 => (TokenType::TypeI8, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEI16 => (TokenType::TypeI16, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEI32 => (TokenType::TypeI32, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEI64 => (TokenType::TypeI64, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEU8 => (TokenType::TypeU8, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEU16 => (TokenType::TypeU16, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEU32 => (TokenType::TypeU32, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEU64 => (TokenType::TypeU64, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEF32 => (TokenType::TypeF32, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEF64 => (TokenType::TypeF64, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEC32 => (TokenType::TypeC32, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPEC64 => (TokenType::TypeC64, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPECHAR => (TokenType::TypeChar, Box::leak(raw_val.into_boxed_str())),
-            RawToken::TYPESTRING => (TokenType::TypeString, Box::leak(raw_val.into_boxed_str())),
+            RawToken::Plus => (TokenType::Plus, raw_val),
+            RawToken::Minus => (TokenType::Minus, raw_val),
+            RawToken::Star => (TokenType::Star, raw_val),
+            RawToken::Slash => (TokenType::Divide, raw_val),
+            RawToken::LESS => (TokenType::Less, raw_val),
+            RawToken::GREATER => (TokenType::Greater, raw_val),
+            RawToken::NOT => (TokenType::Not, raw_val),
+            RawToken::XOR => (TokenType::Xor, raw_val),
+            RawToken::PERCENT => (TokenType::Percent, raw_val),
+            RawToken::OR => (TokenType::Or, raw_val),
+            RawToken::AND => (TokenType::And, raw_val),
+            RawToken::Equal => (TokenType::Equal, raw_val),
+            RawToken::Colon => (TokenType::Colon, raw_val),
+            RawToken::Comma => (TokenType::Comma, raw_val),
+            RawToken::PlusPlus => (TokenType::PlusPlus, raw_val),
+            RawToken::MinusMinus => (TokenType::MinusMinus, raw_val),
+            RawToken::PlusEqual => (TokenType::PlusEqual, raw_val),
+            RawToken::MinusEqual => (TokenType::MinusEqual, raw_val),
+            RawToken::StarEqual => (TokenType::StarEqual, raw_val),
+            RawToken::SlashEqual => (TokenType::DivideEqual, raw_val),
+            RawToken::EqualEqual => (TokenType::EqualEqual, raw_val),
+            RawToken::LessEqual => (TokenType::LessEqual, raw_val),
+            RawToken::Greaterequal => (TokenType::GreaterEqual, raw_val),
+            RawToken::NotEqual => (TokenType::NotEqual, raw_val),
+            RawToken::XorEqual => (TokenType::XorEqual, raw_val),
+            RawToken::PercentEqual => (TokenType::PercentEqual, raw_val),
+            RawToken::OrEqual => (TokenType::OrEqual, raw_val),
+            RawToken::AndEqual => (TokenType::AndEqual, raw_val),
+            RawToken::ShiftLeft => (TokenType::ShiftLeft, raw_val),
+            RawToken::ShiftRight => (TokenType::ShiftRight, raw_val),
+            RawToken::Arrow => (TokenType::Arrow, raw_val),
+            RawToken::OrOr => (TokenType::OrOr, raw_val),
+            RawToken::AndAnd => (TokenType::AndAnd, raw_val),
+            RawToken::OpenParentesis => (TokenType::OpenParenthesis, raw_val),
+            RawToken::CloseParentesis => (TokenType::CloseParenthesis, raw_val),
+            RawToken::OpenSQParentesis => (TokenType::OpenSqParenthesis, raw_val),
+            RawToken::CloseSQParentesis => (TokenType::CloseSqParenthesis, raw_val),
+            RawToken::OpenCurParentesis => (TokenType::OpenCurParenthesis, raw_val),
+            RawToken::CloseCurParentesis => (TokenType::CloseCurParenthesis, raw_val),
+            RawToken::BOOLEAN => (TokenType::Boolean, raw_val),
+            RawToken::STRING => (TokenType::String, &raw_val[1..raw_val.len() - 1]),
+            RawToken::CHAR => (TokenType::Char, &raw_val[1..raw_val.len() - 1]),
+            RawToken::Dot => (TokenType::Dot, raw_val),
+            RawToken::TYPEI8 => (TokenType::TypeI8, raw_val),
+            RawToken::TYPEI16 => (TokenType::TypeI16, raw_val),
+            RawToken::TYPEI32 => (TokenType::TypeI32, raw_val),
+            RawToken::TYPEI64 => (TokenType::TypeI64, raw_val),
+            RawToken::TYPEU8 => (TokenType::TypeU8, raw_val),
+            RawToken::TYPEU16 => (TokenType::TypeU16, raw_val),
+            RawToken::TYPEU32 => (TokenType::TypeU32, raw_val),
+            RawToken::TYPEU64 => (TokenType::TypeU64, raw_val),
+            RawToken::TYPEF32 => (TokenType::TypeF32, raw_val),
+            RawToken::TYPEF64 => (TokenType::TypeF64, raw_val),
+            RawToken::TYPEC32 => (TokenType::TypeC32, raw_val),
+            RawToken::TYPEC64 => (TokenType::TypeC64, raw_val),
+            RawToken::TYPECHAR => (TokenType::TypeChar, raw_val),
+            RawToken::TYPESTRING => (TokenType::TypeString, raw_val),
             RawToken::Number => {
-                if raw_val.as_str().contains('.') {
-                    (TokenType::Double, Box::leak(raw_val.into_boxed_str()))
+                // Same rules as NumericLiteral: a fraction, exponent or `f` suffix makes a float.
+                if raw_val.contains(['.', 'e', 'E']) || raw_val.ends_with('f') {
+                    (TokenType::Double, raw_val)
                 } else {
-                    (TokenType::Integer, Box::leak(raw_val.into_boxed_str()))
+                    (TokenType::Integer, raw_val)
                 }
             }
-            RawToken::TYPEBOOL => (TokenType::TypeBool, Box::leak(raw_val.into_boxed_str())),
+            RawToken::TYPEBOOL => (TokenType::TypeBool, raw_val),
+            RawToken::Invalid => (TokenType::Unknown, raw_val),
             //_ => (TokenType::Unknown,raw_val),
         }
     }
@@ -421,20 +594,15 @@ impl TokenType {
             TokenType::EqualEqual => "EQUALEQUAL_OP",
             TokenType::LessEqual => "LESSEQUAL_OP",
             TokenType::GreaterEqual => "GREATEREQUAL_OP",
+            TokenType::OrEqual => "OREQUAL_OP",
+            TokenType::AndEqual => "ANDEQUAL_OP",
+            TokenType::ShiftLeft => "SHIFTLEFT_OP",
+            TokenType::ShiftRight => "SHIFTRIGHT_OP",
+            TokenType::Arrow => "ARROW",
             TokenType::Identifier => "IDENT",
             TokenType::Char => "CH",
             TokenType::String => "STR",
             TokenType::Eoft => "EOF",
-            TokenType::KMain => "K_MAIN",
-            TokenType::KVar => "K_VAR",
-            TokenType::KIf => "K_IF",
-            TokenType::KWhile => "K_WHILE",
-            TokenType::KElse => "K_ELSE",
-            TokenType::KFor => "K_FOR",
-            TokenType::KBreak => "BREAK",
-            TokenType::KFun => "K_FUN",
-            TokenType::KReturn => "K_RETURN",
-            TokenType::KNullptr => "K_NULLPTR",
             TokenType::OpenParenthesis => "OPEN_PAR",
             TokenType::OpenSqParenthesis => "OPEN_SQ_PAR",
             TokenType::OpenCurParenthesis => "OPEN_CUR_PAR",
@@ -460,6 +628,22 @@ impl TokenType {
             TokenType::TypeString => "STRING",
             TokenType::TypeBool => "BOOL",
             TokenType::Comment => "COMMENT",
+            TokenType::DocComment => "DOC_COMMENT",
+            TokenType::KMain
+            | TokenType::KVar
+            | TokenType::KConst
+            | TokenType::KIf
+            | TokenType::KWhile
+            | TokenType::KElse
+            | TokenType::KFor
+            | TokenType::KBreak
+            | TokenType::KContinue
+            | TokenType::KFun
+            | TokenType::KReturn
+            | TokenType::KNullptr
+            | TokenType::KReserved => {
+                Keyword::find_by_type(self).map_or("UNKNOWN", |keyword| keyword.compact)
+            }
             // Add more cases as needed
             TokenType::Unknown => "UNKNOWN",
         }
@@ -478,7 +662,6 @@ impl fmt::Display for TokenType {
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -494,21 +677,32 @@ mod tests {
         assert!(TokenType::KBreak.is_keyword());
         assert!(TokenType::KFun.is_keyword());
         assert!(TokenType::KReturn.is_keyword());
+        assert!(TokenType::KConst.is_keyword());
+        assert!(TokenType::KContinue.is_keyword());
+        assert!(TokenType::KNullptr.is_keyword());
+        assert!(TokenType::KReserved.is_keyword());
+        assert!(!TokenType::Boolean.is_keyword());
         assert!(!TokenType::Identifier.is_keyword());
     }
 
     #[test]
     fn test_map_keyword_to_token_type() {
-        assert_eq!(TokenType::map_keword_to_token_type("main".to_string()), TokenType::KMain);
-        assert_eq!(TokenType::map_keword_to_token_type("var".to_string()), TokenType::KVar);
-        assert_eq!(TokenType::map_keword_to_token_type("if".to_string()), TokenType::KIf);
-        assert_eq!(TokenType::map_keword_to_token_type("while".to_string()), TokenType::KWhile);
-        assert_eq!(TokenType::map_keword_to_token_type("else".to_string()), TokenType::KElse);
-        assert_eq!(TokenType::map_keword_to_token_type("for".to_string()), TokenType::KFor);
-        assert_eq!(TokenType::map_keword_to_token_type("break".to_string()), TokenType::KBreak);
-        assert_eq!(TokenType::map_keword_to_token_type("fun".to_string()), TokenType::KFun);
-        assert_eq!(TokenType::map_keword_to_token_type("return".to_string()), TokenType::KReturn);
-        assert_eq!(TokenType::map_keword_to_token_type("unknown".to_string()), TokenType::Identifier);
+        assert_eq!(TokenType::map_keword_to_token_type("main"), TokenType::KMain);
+        assert_eq!(TokenType::map_keword_to_token_type("var"), TokenType::KVar);
+        assert_eq!(TokenType::map_keword_to_token_type("if"), TokenType::KIf);
+        assert_eq!(TokenType::map_keword_to_token_type("while"), TokenType::KWhile);
+        assert_eq!(TokenType::map_keword_to_token_type("else"), TokenType::KElse);
+        assert_eq!(TokenType::map_keword_to_token_type("for"), TokenType::KFor);
+        assert_eq!(TokenType::map_keword_to_token_type("break"), TokenType::KBreak);
+        assert_eq!(TokenType::map_keword_to_token_type("fun"), TokenType::KFun);
+        assert_eq!(TokenType::map_keword_to_token_type("return"), TokenType::KReturn);
+        assert_eq!(TokenType::map_keword_to_token_type("const"), TokenType::KConst);
+        assert_eq!(TokenType::map_keword_to_token_type("continue"), TokenType::KContinue);
+        assert_eq!(TokenType::map_keword_to_token_type("nullptr"), TokenType::KNullptr);
+        assert_eq!(TokenType::map_keword_to_token_type("true"), TokenType::Boolean);
+        assert_eq!(TokenType::map_keword_to_token_type("false"), TokenType::Boolean);
+        assert_eq!(TokenType::map_keword_to_token_type("class"), TokenType::KReserved);
+        assert_eq!(TokenType::map_keword_to_token_type("unknown"), TokenType::Identifier);
     }
 
     #[test]
@@ -519,66 +713,80 @@ mod tests {
 
     #[test]
     fn test_map_raw_token_type() {
-        assert_eq!(TokenType::map_raw_token_type(RawToken::IdentifierAscii, "main".to_string()), (TokenType::KMain, "main"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::IdentifierUnicode, "变量".to_string()), (TokenType::Identifier, "变量"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Binary, "##1010".to_string()), (TokenType::Integer, "##1010"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Hexadecimal, "#ff".to_string()), (TokenType::Integer, "#ff"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Octal, "#o23".to_string()), (TokenType::Integer, "#o23"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Whitespace, " ".to_string()), (TokenType::Unknown, " "));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::SingleLineComment, "// comment".to_string()), (TokenType::Comment, "// comment"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::MultiLineComment, "/* comment */".to_string()), (TokenType::Comment, "/* comment */"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Plus, "+".to_string()), (TokenType::Plus, "+"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Minus, "-".to_string()), (TokenType::Minus, "-"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Star, "*".to_string()), (TokenType::Star, "*"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Slash, "/".to_string()), (TokenType::Divide, "/"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::LESS, "<".to_string()), (TokenType::Less, "<"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::GREATER, ">".to_string()), (TokenType::Greater, ">"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::NOT, "!".to_string()), (TokenType::Not, "!"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::XOR, "^".to_string()), (TokenType::Xor, "^"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::PERCENT, "%".to_string()), (TokenType::Percent, "%"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::OR, "|".to_string()), (TokenType::Or, "|"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::AND, "&".to_string()), (TokenType::And, "&"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Equal, "=".to_string()), (TokenType::Equal, "="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Colon, ":".to_string()), (TokenType::Colon, ":"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Comma, ",".to_string()), (TokenType::Comma, ","));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::PlusPlus, "++".to_string()), (TokenType::PlusPlus, "++"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::MinusMinus, "--".to_string()), (TokenType::MinusMinus, "--"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::PlusEqual, "+=".to_string()), (TokenType::PlusEqual, "+="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::MinusEqual, "-=".to_string()), (TokenType::MinusEqual, "-="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::LessEqual, "<=".to_string()), (TokenType::LessEqual, "<="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Greaterequal, ">=".to_string()), (TokenType::GreaterEqual, ">="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::NotEqual, "!=".to_string()), (TokenType::NotEqual, "!="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::XorEqual, "^=".to_string()), (TokenType::XorEqual, "^="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::PercentEqual, "%=".to_string()), (TokenType::PercentEqual, "%="));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::OrOr, "||".to_string()), (TokenType::OrOr, "||"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::AndAnd, "&&".to_string()), (TokenType::AndAnd, "&&"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::OpenParentesis, "(".to_string()), (TokenType::OpenParenthesis, "("));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::CloseParentesis, ")".to_string()), (TokenType::CloseParenthesis, ")"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::OpenSQParentesis, "[".to_string()), (TokenType::OpenSqParenthesis, "["));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::CloseSQParentesis, "]".to_string()), (TokenType::CloseSqParenthesis, "]"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::OpenCurParentesis, "{".to_string()), (TokenType::OpenCurParenthesis, "{"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::CloseCurParentesis, "}".to_string()), (TokenType::CloseCurParenthesis, "}"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::BOOLEAN, "true".to_string()), (TokenType::Boolean, "true"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::STRING, "\"hello\"".to_string()), (TokenType::String, "hello"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::CHAR, "'c'".to_string()), (TokenType::Char, "c"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Dot, ".".to_string()), (TokenType::Dot, "."));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI8, "i8".to_string()), (TokenType::TypeI8, "i8"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI16, "i16".to_string()), (TokenType::TypeI16, "i16"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI32, "i32".to_string()), (TokenType::TypeI32, "i32"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI64, "i64".to_string()), (TokenType::TypeI64, "i64"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU8, "u8".to_string()), (TokenType::TypeU8, "u8"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU16, "u16".to_string()), (TokenType::TypeU16, "u16"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU32, "u32".to_string()), (TokenType::TypeU32, "u32"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU64, "u64".to_string()), (TokenType::TypeU64, "u64"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEF32, "f32".to_string()), (TokenType::TypeF32, "f32"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEF64, "f64".to_string()), (TokenType::TypeF64, "f64"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEC32, "c32".to_string()), (TokenType::TypeC32, "c32"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEC64, "c64".to_string()), (TokenType::TypeC64, "c64"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPECHAR, "char".to_string()), (TokenType::TypeChar, "char"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPESTRING, "string".to_string()), (TokenType::TypeString, "string"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEBOOL, "bool".to_string()), (TokenType::TypeBool, "bool"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Number, "123".to_string()), (TokenType::Integer, "123"));
-        assert_eq!(TokenType::map_raw_token_type(RawToken::Number, "123.45".to_string()), (TokenType::Double, "123.45"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::IdentifierAscii, "main"), (TokenType::KMain, "main"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::IdentifierUnicode, "变量"), (TokenType::Identifier, "变量"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Binary, "##1010"), (TokenType::Integer, "##1010"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Hexadecimal, "#ff"), (TokenType::Integer, "#ff"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Octal, "#o23"), (TokenType::Integer, "#o23"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Whitespace, " "), (TokenType::Unknown, " "));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::SingleLineComment, "// comment"), (TokenType::Comment, "// comment"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::MultiLineComment, "/* comment */"), (TokenType::Comment, "/* comment */"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::SingleLineComment, "/// doc"), (TokenType::DocComment, "/// doc"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::MultiLineComment, "/** doc */"), (TokenType::DocComment, "/** doc */"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Plus, "+"), (TokenType::Plus, "+"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Minus, "-"), (TokenType::Minus, "-"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Star, "*"), (TokenType::Star, "*"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Slash, "/"), (TokenType::Divide, "/"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::LESS, "<"), (TokenType::Less, "<"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::GREATER, ">"), (TokenType::Greater, ">"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::NOT, "!"), (TokenType::Not, "!"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::XOR, "^"), (TokenType::Xor, "^"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::PERCENT, "%"), (TokenType::Percent, "%"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::OR, "|"), (TokenType::Or, "|"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::AND, "&"), (TokenType::And, "&"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Equal, "="), (TokenType::Equal, "="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Colon, ":"), (TokenType::Colon, ":"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Comma, ","), (TokenType::Comma, ","));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::PlusPlus, "++"), (TokenType::PlusPlus, "++"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::MinusMinus, "--"), (TokenType::MinusMinus, "--"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::PlusEqual, "+="), (TokenType::PlusEqual, "+="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::MinusEqual, "-="), (TokenType::MinusEqual, "-="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::StarEqual, "*="), (TokenType::StarEqual, "*="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::SlashEqual, "/="), (TokenType::DivideEqual, "/="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::EqualEqual, "=="), (TokenType::EqualEqual, "=="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::LessEqual, "<="), (TokenType::LessEqual, "<="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Greaterequal, ">="), (TokenType::GreaterEqual, ">="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::NotEqual, "!="), (TokenType::NotEqual, "!="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::XorEqual, "^="), (TokenType::XorEqual, "^="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::PercentEqual, "%="), (TokenType::PercentEqual, "%="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::OrEqual, "|="), (TokenType::OrEqual, "|="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::AndEqual, "&="), (TokenType::AndEqual, "&="));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::ShiftLeft, "<<"), (TokenType::ShiftLeft, "<<"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::ShiftRight, ">>"), (TokenType::ShiftRight, ">>"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Arrow, "->"), (TokenType::Arrow, "->"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::OrOr, "||"), (TokenType::OrOr, "||"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::AndAnd, "&&"), (TokenType::AndAnd, "&&"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::OpenParentesis, "("), (TokenType::OpenParenthesis, "("));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::CloseParentesis, ")"), (TokenType::CloseParenthesis, ")"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::OpenSQParentesis, "["), (TokenType::OpenSqParenthesis, "["));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::CloseSQParentesis, "]"), (TokenType::CloseSqParenthesis, "]"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::OpenCurParentesis, "{"), (TokenType::OpenCurParenthesis, "{"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::CloseCurParentesis, "}"), (TokenType::CloseCurParenthesis, "}"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::BOOLEAN, "true"), (TokenType::Boolean, "true"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::STRING, "\"hello\""), (TokenType::String, "hello"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::STRING, r#""a\"""#), (TokenType::String, r#"a\""#));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::CHAR, "'c'"), (TokenType::Char, "c"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Dot, "."), (TokenType::Dot, "."));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI8, "i8"), (TokenType::TypeI8, "i8"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI16, "i16"), (TokenType::TypeI16, "i16"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI32, "i32"), (TokenType::TypeI32, "i32"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEI64, "i64"), (TokenType::TypeI64, "i64"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU8, "u8"), (TokenType::TypeU8, "u8"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU16, "u16"), (TokenType::TypeU16, "u16"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU32, "u32"), (TokenType::TypeU32, "u32"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEU64, "u64"), (TokenType::TypeU64, "u64"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEF32, "f32"), (TokenType::TypeF32, "f32"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEF64, "f64"), (TokenType::TypeF64, "f64"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEC32, "c32"), (TokenType::TypeC32, "c32"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEC64, "c64"), (TokenType::TypeC64, "c64"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPECHAR, "char"), (TokenType::TypeChar, "char"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPESTRING, "string"), (TokenType::TypeString, "string"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::TYPEBOOL, "bool"), (TokenType::TypeBool, "bool"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Number, "123"), (TokenType::Integer, "123"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Number, "123.45"), (TokenType::Double, "123.45"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Number, "1e3"), (TokenType::Double, "1e3"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Number, "4if"), (TokenType::Double, "4if"));
+        assert_eq!(TokenType::map_raw_token_type(RawToken::Number, "4i"), (TokenType::Integer, "4i"));
     }
 
     #[test]
@@ -612,6 +820,11 @@ mod tests {
         assert_eq!(TokenType::EqualEqual.compact_to_string(), "EQUALEQUAL_OP");
         assert_eq!(TokenType::LessEqual.compact_to_string(), "LESSEQUAL_OP");
         assert_eq!(TokenType::GreaterEqual.compact_to_string(), "GREATEREQUAL_OP");
+        assert_eq!(TokenType::OrEqual.compact_to_string(), "OREQUAL_OP");
+        assert_eq!(TokenType::AndEqual.compact_to_string(), "ANDEQUAL_OP");
+        assert_eq!(TokenType::ShiftLeft.compact_to_string(), "SHIFTLEFT_OP");
+        assert_eq!(TokenType::ShiftRight.compact_to_string(), "SHIFTRIGHT_OP");
+        assert_eq!(TokenType::Arrow.compact_to_string(), "ARROW");
         assert_eq!(TokenType::Identifier.compact_to_string(), "IDENT");
         assert_eq!(TokenType::Char.compact_to_string(), "CH");
         assert_eq!(TokenType::String.compact_to_string(), "STR");
@@ -626,6 +839,9 @@ mod tests {
         assert_eq!(TokenType::KFun.compact_to_string(), "K_FUN");
         assert_eq!(TokenType::KReturn.compact_to_string(), "K_RETURN");
         assert_eq!(TokenType::KNullptr.compact_to_string(), "K_NULLPTR");
+        assert_eq!(TokenType::KConst.compact_to_string(), "K_CONST");
+        assert_eq!(TokenType::KContinue.compact_to_string(), "K_CONTINUE");
+        assert_eq!(TokenType::KReserved.compact_to_string(), "K_RESERVED");
         assert_eq!(TokenType::OpenParenthesis.compact_to_string(), "OPEN_PAR");
         assert_eq!(TokenType::OpenSqParenthesis.compact_to_string(), "OPEN_SQ_PAR");
         assert_eq!(TokenType::OpenCurParenthesis.compact_to_string(), "OPEN_CUR_PAR");
@@ -651,6 +867,7 @@ mod tests {
         assert_eq!(TokenType::TypeString.compact_to_string(), "STRING");
         assert_eq!(TokenType::TypeBool.compact_to_string(), "BOOL");
         assert_eq!(TokenType::Comment.compact_to_string(), "COMMENT");
+        assert_eq!(TokenType::DocComment.compact_to_string(), "DOC_COMMENT");
         assert_eq!(TokenType::Unknown.compact_to_string(), "UNKNOWN");
     }
 
@@ -659,4 +876,48 @@ mod tests {
         assert_eq!(format!("{}", TokenType::Integer), "Integer");
         assert_eq!(format!("{:#}", TokenType::Integer), "INT");
     }
+
+    #[test]
+    fn test_operator_precedence() {
+        assert_eq!(TokenType::Comma.binary_precedence(), Some(1));
+        assert_eq!(TokenType::Equal.binary_precedence(), Some(3));
+        assert!(TokenType::Star.binary_precedence() > TokenType::Plus.binary_precedence());
+        assert!(TokenType::Xor.binary_precedence() > TokenType::Star.binary_precedence());
+        assert_eq!(TokenType::Dot.binary_precedence(), Some(11));
+        assert_eq!(TokenType::Identifier.binary_precedence(), None);
+        assert_eq!(TokenType::Minus.unary_precedence(), Some(12));
+        assert_eq!(TokenType::Star.unary_precedence(), None);
+        assert!(TokenType::Not.unary_precedence() > TokenType::Dot.binary_precedence());
+        assert_eq!(TokenType::ShiftLeft.binary_precedence(), TokenType::Star.binary_precedence());
+        assert_eq!(TokenType::Or.binary_precedence(), TokenType::Plus.binary_precedence());
+    }
+
+    #[test]
+    fn test_every_operator_has_a_precedence() {
+        use TokenType::*;
+        let without_level = [Not, PlusPlus, MinusMinus, Arrow];
+        for operator in TokenType::OPERATORS
+            .iter()
+            .filter(|operator| !without_level.contains(operator))
+        {
+            assert!(operator.binary_precedence().is_some(), "{:?} has no precedence", operator);
+        }
+    }
+
+    #[test]
+    fn test_operator_classes() {
+        assert_eq!(TokenType::PlusEqual.associativity(), Associativity::Right);
+        assert_eq!(TokenType::Minus.associativity(), Associativity::Left);
+        assert!(TokenType::PercentEqual.is_assignment_operator());
+        assert!(TokenType::OrEqual.is_assignment_operator());
+        assert_eq!(TokenType::AndEqual.associativity(), Associativity::Right);
+        assert!(!TokenType::EqualEqual.is_assignment_operator());
+        assert!(TokenType::Dot.is_binary_operator());
+        assert!(!TokenType::Comma.is_binary_operator());
+        assert!(!TokenType::Not.is_binary_operator());
+        assert!(TokenType::Char.is_literal());
+        assert!(!TokenType::KNullptr.is_literal());
+        assert!(TokenType::TypeBool.is_type_keyword());
+        assert!(!TokenType::Identifier.is_type_keyword());
+    }
 }
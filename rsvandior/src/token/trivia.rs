@@ -0,0 +1,56 @@
+use std::fmt;
+
+use super::span::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    Whitespace,
+    Newline,
+    LineComment,
+    BlockComment,
+}
+
+/// Source text with no meaning for the grammar, kept around by the lossless token stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia<'src> {
+    pub kind: TriviaKind,
+    pub span: Span,
+    pub text: &'src str,
+}
+
+impl<'src> Trivia<'src> {
+    pub fn new(kind: TriviaKind, span: Span, text: &'src str) -> Self {
+        Self { kind, span, text }
+    }
+
+    pub fn is_newline(&self) -> bool {
+        self.kind == TriviaKind::Newline
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(
+            self.kind,
+            TriviaKind::LineComment | TriviaKind::BlockComment
+        )
+    }
+}
+
+impl fmt::Display for Trivia<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trivia() {
+        let trivia = Trivia::new(TriviaKind::LineComment, Span::new(0, 4), "// a");
+        assert!(trivia.is_comment());
+        assert!(!trivia.is_newline());
+        assert_eq!(format!("{}", trivia), "// a");
+        assert!(Trivia::new(TriviaKind::Newline, Span::new(4, 5), "\n").is_newline());
+    }
+}
@@ -0,0 +1,128 @@
+//! Golden-file tests: every `tests/lexer/*.vn` file is tokenized and compared with the dump
+//! checked in next to it as `*.tokens`. Run with `VNDR_BLESS=1` to write the current output as
+//! the new expected dump.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rsvandior::lexer::Tokenizer;
+
+const BLESS_VAR: &str = "VNDR_BLESS";
+
+fn dump(file_name: &str, input: &str) -> String {
+    let mut output = String::new();
+    for item in Tokenizer::new(file_name, input) {
+        match item {
+            Ok(token) => {
+                let location = token.source_location;
+                write!(
+                    output,
+                    "{}:{}-{}:{} {}",
+                    location.line,
+                    location.column,
+                    location.end_line,
+                    location.end_column,
+                    token.token_type.compact_to_string()
+                )
+                .unwrap();
+                if !token.value.is_empty() {
+                    write!(output, " '{}'", token.value.escape_debug()).unwrap();
+                }
+                if let Some(numeric) = token.numeric {
+                    write!(output, " = {}", numeric).unwrap();
+                }
+                if let Some(cooked) = token
+                    .cooked
+                    .as_deref()
+                    .filter(|cooked| *cooked != token.value)
+                {
+                    write!(output, " = '{}'", cooked.escape_debug()).unwrap();
+                }
+                writeln!(output).unwrap();
+            }
+            Err(error) => {
+                let location = error.source_location;
+                writeln!(
+                    output,
+                    "{}:{}-{}:{} error[{}] {} '{}'",
+                    location.line,
+                    location.column,
+                    location.end_line,
+                    location.end_column,
+                    error.kind.code(),
+                    error.kind,
+                    error.lexeme.escape_debug()
+                )
+                .unwrap();
+            }
+        }
+    }
+    output
+}
+
+/// First differing line of `expected` and `actual`, as a short report.
+fn diff(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected), Some(actual)) if expected == actual => line += 1,
+            (None, None) => return String::from("  (only line endings differ)"),
+            (expected, actual) => {
+                return format!(
+                    "  line {}:\n    expected: {}\n    actual:   {}",
+                    line,
+                    expected.unwrap_or("<end of file>"),
+                    actual.unwrap_or("<end of file>")
+                )
+            }
+        }
+    }
+}
+
+fn corpus(directory: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(directory)
+        .unwrap_or_else(|err| panic!("cannot read {}: {}", directory.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "vn"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn test_lexer_golden_files() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/lexer");
+    let bless = std::env::var_os(BLESS_VAR).is_some_and(|value| value != "0");
+    let files = corpus(&directory);
+    assert!(!files.is_empty(), "no .vn files in {}", directory.display());
+
+    let mut failures: Vec<String> = Vec::new();
+    for path in files {
+        let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let input = fs::read_to_string(&path).unwrap();
+        let actual = dump(&file_name, &input);
+        let expected_path = path.with_extension("tokens");
+        if bless {
+            fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&expected_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!("{}:\n{}", file_name, diff(&expected, &actual))),
+            Err(_) => failures.push(format!(
+                "{}: missing {}",
+                file_name,
+                expected_path.display()
+            )),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "lexer output changed, rerun with {}=1 to accept it:\n{}",
+        BLESS_VAR,
+        failures.join("\n")
+    );
+}
@@ -12,3 +12,27 @@ TEST_CASE("Factorials are computed", "[factorial]")
   REQUIRE(factorial(3) == 6);
   REQUIRE(factorial(10) == 3628800);
 }
+
+#include <rsvandior/my_header_wrapper.hpp>
+#include <string_view>
+#include <utility>
+
+// Under the address sanitizer, enabled by default in developer mode, a token or buffer that
+// vnd::TokenArray fails to hand back to vndr_token_array_free is reported as a leak.
+TEST_CASE("TokenArray releases the tokens it owns", "[rsvandior]")
+{
+  for(int round = 0; round < 10; ++round) {
+    auto tokens = vnd::wrap_tokenize("test.vn", "var a: i8 = 3 @");
+    REQUIRE(tokens.size() == 8);
+    REQUIRE(std::string_view{tokens[1].value} == "a");
+    REQUIRE(std::string_view{tokens[1].source_location.file_name} == "test.vn");
+    REQUIRE(tokens[6].token_type == TokenType::Unknown);
+    REQUIRE(tokens[7].token_type == TokenType::Eoft);
+
+    vnd::TokenArray moved{std::move(tokens)};
+    REQUIRE(moved.size() == 8);
+
+    moved = vnd::wrap_tokenize("test.vn", "a b");
+    REQUIRE(moved.size() == 3);
+  }
+}